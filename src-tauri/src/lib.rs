@@ -3,12 +3,16 @@ use cpal::{FromSample, Sample};
 use enigo::{Enigo, Key, Keyboard, Settings};
 use image::GenericImageView;
 use parking_lot::Mutex;
+use rubato::{FftFixedIn, Resampler};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::io::{Read, Write};
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 use tauri::{
     image::Image,
     menu::{Menu, MenuItem},
@@ -22,20 +26,155 @@ use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextPar
 // Tray icon ID for accessing tray from shortcut handler
 const TRAY_ID: &str = "main-tray";
 
-// Whisper model information
-#[derive(Clone)]
+const HUGGINGFACE_MODELS_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ModelLanguage {
+    #[default]
+    Multilingual,
+    English,
+}
+
+// Whisper model information. Built-in entries come from `get_builtin_models`; custom entries
+// are read from `custom_models.json` in the app data dir and use the same shape.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 struct ModelInfo {
-    name: &'static str,
-    filename: &'static str,
-    url: &'static str,
+    name: String,
+    filename: String,
+    // Where the model is fetched from: an http(s) URL or a file on this machine
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    local_path: Option<PathBuf>,
     size_mb: u32,
+    // Expected SHA-256 of the file (lowercase hex). Built-in models are pinned; custom entries
+    // without one are checked against the digest the server advertises for the download.
+    #[serde(default)]
+    sha256: Option<String>,
+    #[serde(default)]
+    language: ModelLanguage,
+    #[serde(default, skip_deserializing)]
+    custom: bool,
+}
+
+// SHA-256 of the official ggml files on Hugging Face, by file name. Every built-in model must
+// have an entry here; a built-in without one is installed but reported as unverified.
+const BUILTIN_MODEL_SHA256: &[(&str, &str)] = &[];
+
+impl ModelInfo {
+    fn builtin(name: &str, filename: &str, size_mb: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            filename: filename.to_string(),
+            url: Some(format!("{}/{}", HUGGINGFACE_MODELS_URL, filename)),
+            local_path: None,
+            size_mb,
+            sha256: BUILTIN_MODEL_SHA256
+                .iter()
+                .find(|(file, _)| *file == filename)
+                .map(|(_, sha256)| sha256.to_string()),
+            language: if name.contains(".en") {
+                ModelLanguage::English
+            } else {
+                ModelLanguage::Multilingual
+            },
+            custom: false,
+        }
+    }
+}
+
+// User-defined catalog entries, loaded at startup and edited through commands
+#[derive(Clone, Default)]
+struct ModelCatalog {
+    custom: Arc<Mutex<Vec<ModelInfo>>>,
+}
+
+// Where model files live. `custom` mirrors `AppConfig::models_dir`; `migrating` blocks new
+// downloads while `set_models_directory` moves files around.
+#[derive(Clone, Default)]
+struct ModelsDirectory {
+    custom: Arc<Mutex<Option<PathBuf>>>,
+    migrating: Arc<AtomicBool>,
+}
+
+#[derive(Clone, Serialize, Default)]
+struct MigrationEventPayload {
+    status: &'static str,
+    #[serde(rename = "fileName")]
+    file_name: Option<String>,
+    #[serde(rename = "filesDone")]
+    files_done: usize,
+    #[serde(rename = "filesTotal")]
+    files_total: usize,
+    #[serde(rename = "bytesDone")]
+    bytes_done: u64,
+    #[serde(rename = "bytesTotal")]
+    bytes_total: u64,
+    error: Option<String>,
+}
+
+// Which model, if any, the background watchdog keeps downloaded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ModelWatchdogPolicy {
+    Off,
+    EnsureSelected,
+    #[default]
+    EnsureDefault,
+}
+
+// The watchdog re-checks right away when woken (policy, selection or folder changed) and
+// otherwise polls with a growing interval
+const WATCHDOG_MIN_INTERVAL: Duration = Duration::from_secs(30);
+const WATCHDOG_MAX_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+struct ModelWatchdog {
+    wake: Mutex<mpsc::Sender<()>>,
+}
+
+fn wake_model_watchdog(app: &AppHandle) {
+    if let Some(watchdog) = app.try_state::<ModelWatchdog>() {
+        let _ = watchdog.wake.lock().send(());
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum DownloadStatus {
+    Queued,
+    Paused,
+    Resuming,
     Downloading,
     Completed,
     Failed,
+    VerificationFailed,
+    Cancelled,
+}
+
+impl DownloadStatus {
+    // Queued, paused or transferring: the model cannot be downloaded again or removed
+    fn is_in_progress(self) -> bool {
+        matches!(
+            self,
+            DownloadStatus::Queued
+                | DownloadStatus::Paused
+                | DownloadStatus::Resuming
+                | DownloadStatus::Downloading
+        )
+    }
+
+    fn is_transferring(self) -> bool {
+        matches!(self, DownloadStatus::Resuming | DownloadStatus::Downloading)
+    }
+
+    // Where a download that ended with `err` is left
+    fn for_error(err: &(dyn std::error::Error + 'static)) -> Self {
+        if err.is::<ChecksumMismatchError>() {
+            DownloadStatus::VerificationFailed
+        } else {
+            DownloadStatus::Failed
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -43,7 +182,11 @@ struct DownloadRecord {
     status: DownloadStatus,
     downloaded_bytes: u64,
     total_bytes: Option<u64>,
+    // Byte offset the current transfer resumed from, if a partial file was reused
+    resume_offset: Option<u64>,
     error: Option<String>,
+    // Set by `cancel_model_download`; polled by the download thread between chunks
+    cancel_requested: Arc<AtomicBool>,
 }
 
 impl DownloadRecord {
@@ -52,40 +195,240 @@ impl DownloadRecord {
             status,
             downloaded_bytes: 0,
             total_bytes: None,
+            resume_offset: None,
             error: None,
+            cancel_requested: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+// Upper bound for the exponential backoff between download retries
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+// How often a retry backoff checks whether the download was cancelled
+const RETRY_CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// Non-success HTTP response; never retried since the server gave a definitive answer
+#[derive(Debug)]
+struct HttpStatusError(reqwest::StatusCode);
+
+impl std::fmt::Display for HttpStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to download model: HTTP {}", self.0)
+    }
+}
+
+impl std::error::Error for HttpStatusError {}
+
+// Downloaded file does not hash to the expected SHA-256
+#[derive(Debug)]
+struct ChecksumMismatchError {
+    expected: String,
+    actual: String,
+}
+
+impl std::fmt::Display for ChecksumMismatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Checksum mismatch: expected SHA-256 {}, got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for ChecksumMismatchError {}
+
+// The user cancelled the download; not a failure
+#[derive(Debug)]
+struct DownloadCancelled;
+
+impl std::fmt::Display for DownloadCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Download cancelled")
+    }
+}
+
+impl std::error::Error for DownloadCancelled {}
+
+// Kept free on top of the model itself so the disk is never filled completely
+const DISK_SPACE_HEADROOM_BYTES: u64 = 100 * 1024 * 1024;
+
+#[derive(Debug)]
+struct InsufficientDiskSpaceError {
+    required: u64,
+    available: u64,
+}
+
+impl std::fmt::Display for InsufficientDiskSpaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Not enough disk space: {} needed, {} available",
+            format_bytes(self.required),
+            format_bytes(self.available)
+        )
+    }
+}
+
+impl std::error::Error for InsufficientDiskSpaceError {}
+
+const DEFAULT_MAX_CONCURRENT_DOWNLOADS: usize = 2;
+const MAX_CONCURRENT_DOWNLOADS_LIMIT: usize = 8;
+
+#[derive(Clone, Debug)]
+struct QueuedDownload {
+    model_name: String,
+    overwrite: bool,
+    paused: bool,
+}
+
+// Downloads waiting for a free slot, in start order, and the ones currently running
+struct DownloadQueue {
+    pending: VecDeque<QueuedDownload>,
+    active: HashSet<String>,
+    max_concurrent: usize,
+}
+
+impl Default for DownloadQueue {
+    fn default() -> Self {
+        Self {
+            pending: VecDeque::new(),
+            active: HashSet::new(),
+            max_concurrent: DEFAULT_MAX_CONCURRENT_DOWNLOADS,
+        }
+    }
+}
+
+// Progress events go out at most this often, unless the percentage moved by at least a step
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(250);
+const PROGRESS_PERCENT_STEP: f64 = 1.0;
+// Transfer speed is averaged over this trailing window
+const SPEED_WINDOW: Duration = Duration::from_secs(5);
+
+// Rate-limits download progress events and tracks recent (time, bytes) samples for speed/ETA
+struct ProgressThrottle {
+    last_emit: Option<Instant>,
+    last_percent: Option<f64>,
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl ProgressThrottle {
+    fn new(now: Instant, downloaded: u64) -> Self {
+        Self {
+            last_emit: None,
+            last_percent: None,
+            samples: VecDeque::from([(now, downloaded)]),
+        }
+    }
+
+    fn record(&mut self, now: Instant, downloaded: u64) {
+        self.samples.push_back((now, downloaded));
+        // Keep one sample older than the window so the average always spans all of it
+        while self.samples.len() > 2
+            && self
+                .samples
+                .get(1)
+                .map(|(at, _)| now.duration_since(*at) >= SPEED_WINDOW)
+                .unwrap_or(false)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    fn should_emit(&mut self, now: Instant, percent: Option<f64>) -> bool {
+        let interval_elapsed = self
+            .last_emit
+            .map(|at| now.duration_since(at) >= PROGRESS_EMIT_INTERVAL)
+            .unwrap_or(true);
+        let percent_advanced = match (percent, self.last_percent) {
+            (Some(current), Some(last)) => current - last >= PROGRESS_PERCENT_STEP,
+            _ => false,
+        };
+        // The update reaching 100% always goes out so the UI never stalls just short of done
+        let completed = percent.is_some_and(|current| current >= 100.0)
+            && self.last_percent.is_none_or(|last| last < 100.0);
+        if !interval_elapsed && !percent_advanced && !completed {
+            return false;
+        }
+        self.last_emit = Some(now);
+        self.last_percent = percent;
+        true
+    }
+
+    fn bytes_per_second(&self) -> Option<f64> {
+        let (first_at, first_bytes) = self.samples.front()?;
+        let (last_at, last_bytes) = self.samples.back()?;
+        let elapsed = last_at.duration_since(*first_at).as_secs_f64();
+        if elapsed <= 0.0 {
+            return None;
         }
+        Some(last_bytes.saturating_sub(*first_bytes) as f64 / elapsed)
     }
 }
 
+fn estimate_eta_seconds(
+    downloaded: u64,
+    total_bytes: Option<u64>,
+    bytes_per_second: Option<f64>,
+) -> Option<f64> {
+    let remaining = total_bytes?.saturating_sub(downloaded);
+    let speed = bytes_per_second.filter(|speed| *speed > 0.0)?;
+    Some(remaining as f64 / speed)
+}
+
+// Lock order: `queue` before `inner` whenever both are needed
 #[derive(Clone, Default)]
 struct DownloadManager {
     inner: Arc<Mutex<HashMap<String, DownloadRecord>>>,
+    queue: Arc<Mutex<DownloadQueue>>,
 }
 
 #[derive(Default)]
 struct WhisperRuntime {
     current_model: Option<String>,
-    context: Option<WhisperContext>,
+    engine: Option<Box<dyn TranscriptionEngine>>,
 }
 
-#[derive(Clone, Default)]
+// Builds the engine for a model file. whisper.cpp by default; tests plug in a mock.
+type EngineLoader = Arc<
+    dyn Fn(&Path) -> Result<Box<dyn TranscriptionEngine>, Box<dyn std::error::Error>> + Send + Sync,
+>;
+
+#[derive(Clone)]
 struct WhisperManager {
     inner: Arc<Mutex<WhisperRuntime>>,
+    load_engine: EngineLoader,
+}
+
+impl Default for WhisperManager {
+    fn default() -> Self {
+        Self {
+            inner: Arc::default(),
+            load_engine: Arc::new(|path| Ok(Box::new(WhisperEngine::load(path)?))),
+        }
+    }
 }
 
 #[derive(Clone, Serialize)]
 struct ModelStatus {
     name: String,
     size_mb: u32,
+    is_custom: bool,
     is_downloaded: bool,
     is_downloading: bool,
+    is_queued: bool,
+    is_paused: bool,
+    queue_position: Option<usize>,
     is_active: bool,
+    // Downloaded and matched a reference checksum
+    is_verified: bool,
     downloaded_bytes: u64,
     total_bytes: Option<u64>,
+    resume_offset: Option<u64>,
     error: Option<String>,
 }
 
-#[derive(Clone, Serialize)]
+#[derive(Clone, Default, Serialize)]
 struct DownloadEventPayload {
     #[serde(rename = "modelName")]
     model_name: String,
@@ -97,6 +440,28 @@ struct DownloadEventPayload {
     percent: Option<f64>,
     status: &'static str,
     error: Option<String>,
+    #[serde(rename = "resumeOffset")]
+    resume_offset: Option<u64>,
+    #[serde(rename = "bytesPerSecond")]
+    bytes_per_second: Option<f64>,
+    #[serde(rename = "etaSeconds")]
+    eta_seconds: Option<f64>,
+    // Set on "completed": false when no reference checksum was available to check the file
+    verified: Option<bool>,
+}
+
+#[derive(Clone, Serialize)]
+struct QueueEntry {
+    model_name: String,
+    paused: bool,
+}
+
+#[derive(Clone, Serialize)]
+struct ModelVerification {
+    model_name: String,
+    sha256: String,
+    expected_sha256: String,
+    verified: bool,
 }
 
 #[derive(Clone, Serialize)]
@@ -105,9 +470,188 @@ struct ActiveModelPayload {
     model_name: Option<String>,
 }
 
+// Alternative host for the built-in models, e.g. an internal artifact proxy. Files are expected
+// at `<base_url>/<filename>`, mirroring the Hugging Face layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DownloadMirror {
+    base_url: String,
+    // Optional header sent with every request to this mirror, e.g. `Authorization: Bearer ...`
+    #[serde(default)]
+    auth_header_name: Option<String>,
+    #[serde(default)]
+    auth_header_value: Option<String>,
+}
+
+// HTTP settings shared by every download path. Changing them rebuilds the clients in `HttpClients`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct NetworkSettings {
+    // e.g. `http://proxy.corp:3128`; when unset the system proxy environment variables apply
+    proxy_url: Option<String>,
+    // PEM files with extra trusted roots, e.g. the CA of a TLS-inspecting proxy
+    extra_root_certificates: Vec<PathBuf>,
+    connect_timeout_secs: u64,
+    // Applies to each read of the response body, so slow but steady downloads are unaffected
+    read_timeout_secs: u64,
+    // Retries per download source, each resuming from the partial file
+    max_retries: u32,
+    // Delay before the first retry; doubled for every further attempt
+    retry_backoff_ms: u64,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            proxy_url: None,
+            extra_root_certificates: Vec::new(),
+            connect_timeout_secs: 15,
+            read_timeout_secs: 30,
+            max_retries: 2,
+            retry_backoff_ms: 2000,
+        }
+    }
+}
+
+impl NetworkSettings {
+    fn validate(&self) -> Result<(), String> {
+        if !(1..=300).contains(&self.connect_timeout_secs) {
+            return Err("Connect timeout must be between 1 and 300 seconds.".to_string());
+        }
+        if !(1..=3600).contains(&self.read_timeout_secs) {
+            return Err("Read timeout must be between 1 and 3600 seconds.".to_string());
+        }
+        if self.max_retries > 10 {
+            return Err("At most 10 retries are allowed.".to_string());
+        }
+        if self.retry_backoff_ms > MAX_RETRY_BACKOFF.as_millis() as u64 {
+            return Err(format!(
+                "Retry backoff must be at most {} seconds.",
+                MAX_RETRY_BACKOFF.as_secs()
+            ));
+        }
+        Ok(())
+    }
+
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        let factor = 1u64 << attempt.saturating_sub(1).min(16);
+        Duration::from_millis(self.retry_backoff_ms.saturating_mul(factor)).min(MAX_RETRY_BACKOFF)
+    }
+}
+
+#[derive(Clone)]
+struct HttpClientSet {
+    settings: NetworkSettings,
+    // Never follows redirects by itself: checksum lookups read headers off the redirect
+    // response, and transfers follow redirects in `send_download_request`
+    client: reqwest::blocking::Client,
+}
+
+impl HttpClientSet {
+    fn build(settings: &NetworkSettings) -> Result<Self, String> {
+        settings.validate()?;
+        Ok(Self {
+            settings: settings.clone(),
+            client: build_http_client(settings, reqwest::redirect::Policy::none())?,
+        })
+    }
+}
+
+// Managed state holding the configured clients; download threads take a snapshot when they start
+#[derive(Clone)]
+struct HttpClients {
+    inner: Arc<Mutex<HttpClientSet>>,
+}
+
+impl HttpClients {
+    fn new(clients: HttpClientSet) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(clients)),
+        }
+    }
+
+    fn snapshot(&self) -> HttpClientSet {
+        self.inner.lock().clone()
+    }
+}
+
+fn build_http_client(
+    settings: &NetworkSettings,
+    redirect: reqwest::redirect::Policy,
+) -> Result<reqwest::blocking::Client, String> {
+    let mut builder = reqwest::blocking::Client::builder()
+        .user_agent(format!("Sotto/{}", env!("CARGO_PKG_VERSION")))
+        .connect_timeout(Duration::from_secs(settings.connect_timeout_secs))
+        .timeout(Duration::from_secs(settings.read_timeout_secs))
+        .redirect(redirect);
+
+    if let Some(proxy_url) = &settings.proxy_url {
+        let proxy = reqwest::Proxy::all(proxy_url)
+            .map_err(|e| format!("Invalid proxy URL '{}': {}", proxy_url, e))?;
+        builder = builder.proxy(proxy);
+    }
+
+    for path in &settings.extra_root_certificates {
+        let pem = fs::read(path)
+            .map_err(|e| format!("Failed to read certificate {}: {}", path.display(), e))?;
+        let certificates = reqwest::Certificate::from_pem_bundle(&pem)
+            .map_err(|e| format!("Invalid PEM certificate {}: {}", path.display(), e))?;
+        if certificates.is_empty() {
+            return Err(format!("No certificates found in {}", path.display()));
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    builder
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+// A concrete URL to fetch a model from, plus the auth header of the mirror it belongs to
+#[derive(Debug, Clone)]
+struct DownloadSource {
+    url: String,
+    auth_header: Option<(String, String)>,
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 struct AppConfig {
     selected_model: Option<String>,
+    // SHA-256 of each model as verified at download time, used by `verify_model` offline
+    #[serde(default)]
+    model_checksums: HashMap<String, String>,
+    max_concurrent_downloads: Option<usize>,
+    // Tried in order before huggingface.co for built-in models
+    #[serde(default)]
+    download_mirrors: Vec<DownloadMirror>,
+    // Only use the mirrors, e.g. on networks where huggingface.co is blocked
+    #[serde(default)]
+    disable_upstream_fallback: bool,
+    #[serde(default)]
+    network: NetworkSettings,
+    // Models folder chosen by the user; `app_data_dir()/models` when unset
+    models_dir: Option<PathBuf>,
+    #[serde(default)]
+    model_watchdog: ModelWatchdogPolicy,
+    // Models the user removed; the watchdog never downloads these again on its own
+    #[serde(default)]
+    removed_models: HashSet<String>,
+    // Transcribe while the hotkey is held instead of only after release
+    #[serde(default)]
+    streaming_transcription: bool,
+    #[serde(default)]
+    languages: LanguageSettings,
+    #[serde(default)]
+    prompts: PromptSettings,
+    #[serde(default)]
+    decoding: DecodingSettings,
+    #[serde(default)]
+    audio: AudioSettings,
+    #[serde(default)]
+    vad: VadSettings,
+    #[serde(default)]
+    hands_free: HandsFreeSettings,
 }
 
 fn get_config_path(app: &AppHandle) -> Result<PathBuf, String> {
@@ -137,10 +681,27 @@ fn save_app_config(app: &AppHandle, config: &AppConfig) -> Result<(), String> {
     fs::write(&path, serialized).map_err(|e| format!("Failed to write app config: {}", e))
 }
 
+// Held across each load-modify-save of settings.json, which commands and download threads
+// may do at the same time
+static APP_CONFIG_WRITE: Mutex<()> = Mutex::new(());
+
+// Apply `update` to the stored settings and save them. Nothing is written if the current
+// settings cannot be read, so a bad read never replaces them with defaults.
+fn update_app_config(
+    app: &AppHandle,
+    update: impl FnOnce(&mut AppConfig),
+) -> Result<AppConfig, String> {
+    let _guard = APP_CONFIG_WRITE.lock();
+    let mut config = load_app_config(app)?;
+    update(&mut config);
+    save_app_config(app, &config)?;
+    Ok(config)
+}
+
 fn load_selected_model(app: &AppHandle) -> Option<String> {
     match load_app_config(app) {
         Ok(config) => config.selected_model.and_then(|name| {
-            if find_model_info(&name).is_some() {
+            if find_model_info(app, &name).is_some() {
                 Some(name)
             } else {
                 eprintln!(
@@ -158,66 +719,259 @@ fn load_selected_model(app: &AppHandle) -> Option<String> {
 }
 
 fn persist_selected_model(app: &AppHandle, model_name: &str) {
-    let mut config = load_app_config(app).unwrap_or_else(|err| {
-        eprintln!("Failed to load existing app config: {}", err);
-        AppConfig::default()
+    let result = update_app_config(app, |config| {
+        config.selected_model = Some(model_name.to_string());
     });
-    config.selected_model = Some(model_name.to_string());
-    if let Err(err) = save_app_config(app, &config) {
+    if let Err(err) = result {
         eprintln!("Failed to persist selected model '{}': {}", model_name, err);
     }
 }
 
-// Available Whisper models - all models from whisper.cpp repository with correct sizes
-fn get_available_models() -> Vec<ModelInfo> {
+fn set_model_removed(app: &AppHandle, model_name: &str, removed: bool) {
+    let result = update_app_config(app, |config| {
+        if removed {
+            config.removed_models.insert(model_name.to_string());
+        } else {
+            config.removed_models.remove(model_name);
+        }
+    });
+    if let Err(err) = result {
+        eprintln!("Failed to record removal of '{}': {}", model_name, err);
+    }
+}
+
+fn record_model_checksum(app: &AppHandle, model_name: &str, sha256: &str) {
+    let result = update_app_config(app, |config| {
+        config
+            .model_checksums
+            .insert(model_name.to_string(), sha256.to_string());
+    });
+    if let Err(err) = result {
+        eprintln!("Failed to record checksum for '{}': {}", model_name, err);
+    }
+}
+
+// Rewrite a catalog URL onto each configured mirror. Only URLs under the Hugging Face base are
+// rewritten; custom models keep their own URL.
+fn resolve_download_sources(config: &AppConfig, url: &str) -> Vec<DownloadSource> {
+    let Some(relative) = url.strip_prefix(HUGGINGFACE_MODELS_URL) else {
+        return vec![DownloadSource {
+            url: url.to_string(),
+            auth_header: None,
+        }];
+    };
+
+    let mut sources: Vec<DownloadSource> = config
+        .download_mirrors
+        .iter()
+        .map(|mirror| DownloadSource {
+            url: format!("{}{}", mirror.base_url.trim_end_matches('/'), relative),
+            auth_header: mirror
+                .auth_header_name
+                .clone()
+                .zip(mirror.auth_header_value.clone()),
+        })
+        .collect();
+    if sources.is_empty() || !config.disable_upstream_fallback {
+        sources.push(DownloadSource {
+            url: url.to_string(),
+            auth_header: None,
+        });
+    }
+    sources
+}
+
+fn validate_download_mirror(mirror: &mut DownloadMirror) -> Result<(), String> {
+    mirror.base_url = mirror.base_url.trim().to_string();
+    let parsed = reqwest::Url::parse(&mirror.base_url)
+        .map_err(|e| format!("Invalid mirror URL '{}': {}", mirror.base_url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!(
+            "Mirror URL '{}' must use http or https.",
+            mirror.base_url
+        ));
+    }
+
+    mirror.auth_header_name = mirror
+        .auth_header_name
+        .take()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    match (&mirror.auth_header_name, &mirror.auth_header_value) {
+        (Some(name), Some(value)) => {
+            reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("Invalid auth header name '{}'.", name))?;
+            reqwest::header::HeaderValue::from_str(value)
+                .map_err(|_| format!("Invalid value for auth header '{}'.", name))?;
+        }
+        (None, _) => mirror.auth_header_value = None,
+        (Some(name), None) => {
+            return Err(format!("Auth header '{}' has no value.", name));
+        }
+    }
+    Ok(())
+}
+
+// Built-in Whisper models - all models from whisper.cpp repository with correct sizes
+fn get_builtin_models() -> Vec<ModelInfo> {
     vec![
         // Tiny models
-        ModelInfo { name: "tiny", filename: "ggml-tiny.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin", size_mb: 75 },
-        ModelInfo { name: "tiny.en", filename: "ggml-tiny.en.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.en.bin", size_mb: 75 },
-        ModelInfo { name: "tiny-q5_1", filename: "ggml-tiny-q5_1.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny-q5_1.bin", size_mb: 31 },
-        ModelInfo { name: "tiny.en-q5_1", filename: "ggml-tiny.en-q5_1.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.en-q5_1.bin", size_mb: 31 },
-        ModelInfo { name: "tiny-q8_0", filename: "ggml-tiny-q8_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny-q8_0.bin", size_mb: 42 },
-        ModelInfo { name: "tiny.en-q8_0", filename: "ggml-tiny.en-q8_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.en-q8_0.bin", size_mb: 42 },
-
+        ModelInfo::builtin("tiny", "ggml-tiny.bin", 75),
+        ModelInfo::builtin("tiny.en", "ggml-tiny.en.bin", 75),
+        ModelInfo::builtin("tiny-q5_1", "ggml-tiny-q5_1.bin", 31),
+        ModelInfo::builtin("tiny.en-q5_1", "ggml-tiny.en-q5_1.bin", 31),
+        ModelInfo::builtin("tiny-q8_0", "ggml-tiny-q8_0.bin", 42),
+        ModelInfo::builtin("tiny.en-q8_0", "ggml-tiny.en-q8_0.bin", 42),
         // Base models
-        ModelInfo { name: "base", filename: "ggml-base.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin", size_mb: 142 },
-        ModelInfo { name: "base.en", filename: "ggml-base.en.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.en.bin", size_mb: 142 },
-        ModelInfo { name: "base-q5_1", filename: "ggml-base-q5_1.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base-q5_1.bin", size_mb: 57 },
-        ModelInfo { name: "base.en-q5_1", filename: "ggml-base.en-q5_1.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.en-q5_1.bin", size_mb: 57 },
-        ModelInfo { name: "base-q8_0", filename: "ggml-base-q8_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base-q8_0.bin", size_mb: 78 },
-        ModelInfo { name: "base.en-q8_0", filename: "ggml-base.en-q8_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.en-q8_0.bin", size_mb: 78 },
-
+        ModelInfo::builtin("base", "ggml-base.bin", 142),
+        ModelInfo::builtin("base.en", "ggml-base.en.bin", 142),
+        ModelInfo::builtin("base-q5_1", "ggml-base-q5_1.bin", 57),
+        ModelInfo::builtin("base.en-q5_1", "ggml-base.en-q5_1.bin", 57),
+        ModelInfo::builtin("base-q8_0", "ggml-base-q8_0.bin", 78),
+        ModelInfo::builtin("base.en-q8_0", "ggml-base.en-q8_0.bin", 78),
         // Small models
-        ModelInfo { name: "small", filename: "ggml-small.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin", size_mb: 466 },
-        ModelInfo { name: "small.en", filename: "ggml-small.en.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.en.bin", size_mb: 466 },
-        ModelInfo { name: "small-q5_1", filename: "ggml-small-q5_1.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small-q5_1.bin", size_mb: 181 },
-        ModelInfo { name: "small.en-q5_1", filename: "ggml-small.en-q5_1.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.en-q5_1.bin", size_mb: 181 },
-        ModelInfo { name: "small-q8_0", filename: "ggml-small-q8_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small-q8_0.bin", size_mb: 252 },
-        ModelInfo { name: "small.en-q8_0", filename: "ggml-small.en-q8_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.en-q8_0.bin", size_mb: 252 },
-
+        ModelInfo::builtin("small", "ggml-small.bin", 466),
+        ModelInfo::builtin("small.en", "ggml-small.en.bin", 466),
+        ModelInfo::builtin("small-q5_1", "ggml-small-q5_1.bin", 181),
+        ModelInfo::builtin("small.en-q5_1", "ggml-small.en-q5_1.bin", 181),
+        ModelInfo::builtin("small-q8_0", "ggml-small-q8_0.bin", 252),
+        ModelInfo::builtin("small.en-q8_0", "ggml-small.en-q8_0.bin", 252),
         // Medium models
-        ModelInfo { name: "medium", filename: "ggml-medium.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.bin", size_mb: 1536 },
-        ModelInfo { name: "medium.en", filename: "ggml-medium.en.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.en.bin", size_mb: 1536 },
-        ModelInfo { name: "medium-q5_0", filename: "ggml-medium-q5_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium-q5_0.bin", size_mb: 514 },
-        ModelInfo { name: "medium.en-q5_0", filename: "ggml-medium.en-q5_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.en-q5_0.bin", size_mb: 514 },
-        ModelInfo { name: "medium-q8_0", filename: "ggml-medium-q8_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium-q8_0.bin", size_mb: 785 },
-        ModelInfo { name: "medium.en-q8_0", filename: "ggml-medium.en-q8_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.en-q8_0.bin", size_mb: 785 },
-
+        ModelInfo::builtin("medium", "ggml-medium.bin", 1536),
+        ModelInfo::builtin("medium.en", "ggml-medium.en.bin", 1536),
+        ModelInfo::builtin("medium-q5_0", "ggml-medium-q5_0.bin", 514),
+        ModelInfo::builtin("medium.en-q5_0", "ggml-medium.en-q5_0.bin", 514),
+        ModelInfo::builtin("medium-q8_0", "ggml-medium-q8_0.bin", 785),
+        ModelInfo::builtin("medium.en-q8_0", "ggml-medium.en-q8_0.bin", 785),
         // Large models
-        ModelInfo { name: "large-v3", filename: "ggml-large-v3.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3.bin", size_mb: 2965 },
-        ModelInfo { name: "large-v3-q5_0", filename: "ggml-large-v3-q5_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3-q5_0.bin", size_mb: 1126 },
-        ModelInfo { name: "large-v3-turbo", filename: "ggml-large-v3-turbo.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3-turbo.bin", size_mb: 1536 },
-        ModelInfo { name: "large-v3-turbo-q5_0", filename: "ggml-large-v3-turbo-q5_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3-turbo-q5_0.bin", size_mb: 547 },
-        ModelInfo { name: "large-v3-turbo-q8_0", filename: "ggml-large-v3-turbo-q8_0.bin", url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3-turbo-q8_0.bin", size_mb: 834 },
+        ModelInfo::builtin("large-v3", "ggml-large-v3.bin", 2965),
+        ModelInfo::builtin("large-v3-q5_0", "ggml-large-v3-q5_0.bin", 1126),
+        ModelInfo::builtin("large-v3-turbo", "ggml-large-v3-turbo.bin", 1536),
+        ModelInfo::builtin("large-v3-turbo-q5_0", "ggml-large-v3-turbo-q5_0.bin", 547),
+        ModelInfo::builtin("large-v3-turbo-q8_0", "ggml-large-v3-turbo-q8_0.bin", 834),
     ]
 }
 
-fn find_model_info<'a>(model_name: &str) -> Option<ModelInfo> {
-    get_available_models()
+// Built-in models followed by the user's custom catalog entries
+fn get_available_models(app: &AppHandle) -> Vec<ModelInfo> {
+    let mut models = get_builtin_models();
+    if let Some(catalog) = app.try_state::<ModelCatalog>() {
+        models.extend(catalog.inner().custom.lock().iter().cloned());
+    }
+    models
+}
+
+fn find_model_info(app: &AppHandle, model_name: &str) -> Option<ModelInfo> {
+    get_available_models(app)
         .into_iter()
         .find(|m| m.name == model_name)
 }
 
+fn get_custom_catalog_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let base_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|_| "Failed to get app data dir".to_string())?;
+    fs::create_dir_all(&base_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    Ok(base_dir.join("custom_models.json"))
+}
+
+// Read the custom catalog, dropping (and logging) entries that fail validation
+fn load_custom_models(app: &AppHandle) -> Result<Vec<ModelInfo>, String> {
+    let path = get_custom_catalog_path(app)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read custom model catalog: {}", e))?;
+    let entries: Vec<ModelInfo> = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse custom model catalog: {}", e))?;
+
+    let mut models: Vec<ModelInfo> = Vec::new();
+    for mut entry in entries {
+        entry.custom = true;
+        match validate_custom_model(&mut entry, &models, false) {
+            Ok(()) => models.push(entry),
+            Err(err) => eprintln!("Skipping custom model '{}': {}", entry.name, err),
+        }
+    }
+    Ok(models)
+}
+
+fn save_custom_models(app: &AppHandle, models: &[ModelInfo]) -> Result<(), String> {
+    let path = get_custom_catalog_path(app)?;
+    let serialized = serde_json::to_string_pretty(models)
+        .map_err(|e| format!("Failed to serialize custom model catalog: {}", e))?;
+    fs::write(&path, serialized).map_err(|e| format!("Failed to write custom model catalog: {}", e))
+}
+
+// Check a custom entry against the built-in list and the other custom entries. Normalizes the
+// checksum to lowercase. `check_source` also requires a local source file to exist, which is
+// skipped on load since the model may already have been copied into the models folder.
+fn validate_custom_model(
+    entry: &mut ModelInfo,
+    others: &[ModelInfo],
+    check_source: bool,
+) -> Result<(), String> {
+    let name = entry.name.trim();
+    if name.is_empty() {
+        return Err("Model name is required.".to_string());
+    }
+    entry.name = name.to_string();
+
+    let existing = get_builtin_models()
+        .into_iter()
+        .chain(others.iter().cloned());
+    for model in existing {
+        if model.name == entry.name {
+            return Err(format!("A model named '{}' already exists.", entry.name));
+        }
+        if model.filename == entry.filename {
+            return Err(format!(
+                "File name '{}' is already used by model '{}'.",
+                entry.filename, model.name
+            ));
+        }
+    }
+
+    if entry.filename.is_empty()
+        || entry.filename.contains(['/', '\\'])
+        || entry.filename.starts_with('.')
+        || !entry.filename.ends_with(".bin")
+    {
+        return Err("File name must be a plain '.bin' file name.".to_string());
+    }
+
+    match (&entry.url, &entry.local_path) {
+        (Some(url), None) => {
+            if !(url.starts_with("https://") || url.starts_with("http://")) {
+                return Err("Model URL must start with http:// or https://.".to_string());
+            }
+        }
+        (None, Some(path)) => {
+            if check_source && !path.is_file() {
+                return Err(format!("Model file not found: {}", path.display()));
+            }
+        }
+        _ => return Err("Provide either a download URL or a local path.".to_string()),
+    }
+
+    if entry.size_mb == 0 {
+        return Err("Model size must be greater than zero.".to_string());
+    }
+
+    if let Some(sha256) = &entry.sha256 {
+        let normalized = sha256.trim().to_ascii_lowercase();
+        if !is_sha256_hex(&normalized) {
+            return Err("Checksum must be a 64-character SHA-256 hex digest.".to_string());
+        }
+        entry.sha256 = Some(normalized);
+    }
+
+    Ok(())
+}
+
 fn emit_download_event(app: &AppHandle, payload: DownloadEventPayload) {
     let _ = app.emit("model-download-progress", payload);
 }
@@ -229,9 +983,14 @@ fn spawn_model_download(
     model_name: String,
     overwrite: bool,
 ) -> Result<(), String> {
-    let model_info =
-        find_model_info(&model_name).ok_or_else(|| "Unknown model name".to_string())?;
-    let model_path = get_model_path_for(app, &model_name);
+    find_model_info(app, &model_name).ok_or_else(|| "Unknown model name".to_string())?;
+    if app
+        .try_state::<ModelsDirectory>()
+        .is_some_and(|dir| dir.migrating.load(Ordering::SeqCst))
+    {
+        return Err("The models folder is being moved. Try again when it is done.".to_string());
+    }
+    let model_path = get_model_path_for(app, &model_name)?;
 
     if model_path.exists() && !overwrite {
         return Err("Model already downloaded".to_string());
@@ -240,16 +999,22 @@ fn spawn_model_download(
     {
         let mut map = downloads.inner.lock();
         if let Some(entry) = map.get(&model_name) {
-            if entry.status == DownloadStatus::Downloading {
+            if entry.status.is_in_progress() {
                 return Err("Download already in progress".to_string());
             }
         }
         map.insert(
             model_name.clone(),
-            DownloadRecord::new(DownloadStatus::Downloading),
+            DownloadRecord::new(DownloadStatus::Queued),
         );
     }
 
+    downloads.queue.lock().pending.push_back(QueuedDownload {
+        model_name: model_name.clone(),
+        overwrite,
+        paused: false,
+    });
+
     emit_download_event(
         app,
         DownloadEventPayload {
@@ -259,132 +1024,224 @@ fn spawn_model_download(
             percent: None,
             status: if overwrite { "refreshing" } else { "queued" },
             error: None,
+            ..Default::default()
         },
     );
+    emit_queue_changed(app, &downloads);
 
-    let app_handle = app.clone();
-    let downloads_clone = downloads.clone();
-    let whisper_clone = whisper.clone();
-
-    std::thread::spawn(move || {
-        download_model_task(
-            app_handle,
-            downloads_clone,
-            whisper_clone,
-            model_name,
-            model_info,
-            overwrite,
-        );
-    });
+    pump_download_queue(app, &downloads, &whisper);
 
     Ok(())
 }
 
-fn download_model_task(
-    app: AppHandle,
-    downloads: DownloadManager,
-    whisper: WhisperManager,
-    model_name: String,
-    model_info: ModelInfo,
-    overwrite: bool,
-) {
-    let model_path = get_model_path_for(&app, &model_name);
-    let temp_path = model_path.with_extension("download");
-
-    let result: Result<(), Box<dyn std::error::Error>> = (|| {
-        if let Some(parent) = model_path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
-        if temp_path.exists() {
-            let _ = std::fs::remove_file(&temp_path);
-        }
-
-        let mut response = reqwest::blocking::get(model_info.url)?;
-
-        if !response.status().is_success() {
-            return Err(format!("Failed to download model: HTTP {}", response.status()).into());
-        }
-
-        let total_bytes = response.content_length();
+// Start queued downloads until the concurrency limit is reached. Each download thread pumps
+// the queue again when it finishes.
+fn pump_download_queue(app: &AppHandle, downloads: &DownloadManager, whisper: &WhisperManager) {
+    loop {
+        let next = {
+            let mut queue = downloads.queue.lock();
+            if queue.active.len() >= queue.max_concurrent {
+                return;
+            }
+            let Some(index) = queue.pending.iter().position(|item| !item.paused) else {
+                return;
+            };
+            let Some(item) = queue.pending.remove(index) else {
+                return;
+            };
+            queue.active.insert(item.model_name.clone());
+            item
+        };
+
+        // The model can disappear from the catalog while queued, e.g. a custom entry removed
+        let resolved = find_model_info(app, &next.model_name)
+            .ok_or_else(|| format!("Model '{}' is no longer in the catalog", next.model_name))
+            .and_then(|info| Ok((get_model_base_path(app)?.join(&info.filename), info)));
+        let (model_path, model_info) = match resolved {
+            Ok(resolved) => resolved,
+            Err(message) => {
+                downloads.queue.lock().active.remove(&next.model_name);
+                {
+                    let mut map = downloads.inner.lock();
+                    if let Some(entry) = map.get_mut(&next.model_name) {
+                        entry.status = DownloadStatus::Failed;
+                        entry.error = Some(message.clone());
+                    }
+                }
+                emit_download_event(
+                    app,
+                    DownloadEventPayload {
+                        model_name: next.model_name.clone(),
+                        status: "error",
+                        error: Some(message),
+                        ..Default::default()
+                    },
+                );
+                emit_queue_changed(app, downloads);
+                continue;
+            }
+        };
 
         {
             let mut map = downloads.inner.lock();
-            if let Some(entry) = map.get_mut(&model_name) {
+            if let Some(entry) = map.get_mut(&next.model_name) {
                 entry.status = DownloadStatus::Downloading;
-                entry.downloaded_bytes = 0;
-                entry.total_bytes = total_bytes;
-                entry.error = None;
             }
         }
+        emit_queue_changed(app, downloads);
+
+        let app_handle = app.clone();
+        let downloads_clone = downloads.clone();
+        let whisper_clone = whisper.clone();
+
+        std::thread::spawn(move || {
+            download_model_task(
+                app_handle.clone(),
+                downloads_clone.clone(),
+                whisper_clone.clone(),
+                next.model_name.clone(),
+                model_info,
+                model_path,
+                next.overwrite,
+            );
 
-        emit_download_event(
-            &app,
-            DownloadEventPayload {
-                model_name: model_name.clone(),
-                downloaded_bytes: 0,
-                total_bytes,
-                percent: total_bytes.map(|_| 0.0),
-                status: "started",
-                error: None,
-            },
-        );
+            downloads_clone.queue.lock().active.remove(&next.model_name);
+            pump_download_queue(&app_handle, &downloads_clone, &whisper_clone);
+        });
+    }
+}
+
+fn queue_snapshot(downloads: &DownloadManager) -> Vec<QueueEntry> {
+    downloads
+        .queue
+        .lock()
+        .pending
+        .iter()
+        .map(|item| QueueEntry {
+            model_name: item.model_name.clone(),
+            paused: item.paused,
+        })
+        .collect()
+}
 
-        let mut file = std::fs::File::create(&temp_path)?;
-        let mut buffer = [0u8; 1024 * 64];
-        let mut downloaded: u64 = 0;
+fn emit_queue_changed(app: &AppHandle, downloads: &DownloadManager) {
+    let _ = app.emit("download-queue-changed", queue_snapshot(downloads));
+}
 
-        loop {
-            let bytes_read = response.read(&mut buffer)?;
-            if bytes_read == 0 {
-                break;
-            }
-            file.write_all(&buffer[..bytes_read])?;
-            downloaded += bytes_read as u64;
+fn download_model_task(
+    app: AppHandle,
+    downloads: DownloadManager,
+    whisper: WhisperManager,
+    model_name: String,
+    model_info: ModelInfo,
+    model_path: PathBuf,
+    overwrite: bool,
+) {
+    let temp_path = get_temp_path_for(&model_path);
+    let cancel_requested = downloads
+        .inner
+        .lock()
+        .get(&model_name)
+        .map(|entry| entry.cancel_requested.clone())
+        .unwrap_or_default();
+    let http_state: tauri::State<HttpClients> = app.state();
+    let http = http_state.inner().snapshot();
+
+    let result: Result<Option<String>, Box<dyn std::error::Error>> = (|| {
+        if let Some(parent) = model_path.parent() {
+            std::fs::create_dir_all(parent)?;
 
-            {
-                let mut map = downloads.inner.lock();
-                if let Some(entry) = map.get_mut(&model_name) {
-                    entry.downloaded_bytes = downloaded;
-                    entry.total_bytes = total_bytes;
-                }
+            // Rough check against the catalog size before contacting the server; the transfer
+            // checks again once the exact length is known
+            let partial_bytes = std::fs::metadata(&temp_path)
+                .map(|meta| meta.len())
+                .unwrap_or(0);
+            let estimated_bytes = u64::from(model_info.size_mb) * 1024 * 1024;
+            ensure_disk_space(parent, estimated_bytes.saturating_sub(partial_bytes))?;
+        }
+
+        let sources = match model_info.url.as_deref() {
+            Some(url) => {
+                let config = load_app_config(&app).unwrap_or_else(|err| {
+                    eprintln!("Failed to load app config: {}", err);
+                    AppConfig::default()
+                });
+                resolve_download_sources(&config, url)
             }
+            None => Vec::new(),
+        };
+
+        let expected_sha256 = expected_model_sha256(&model_info, || {
+            sources
+                .iter()
+                .find_map(|source| fetch_advertised_sha256(&http.client, source))
+        });
+        if expected_sha256.is_none() {
+            eprintln!(
+                "No reference checksum available for '{}'; it will be installed unverified",
+                model_name
+            );
+        }
 
-            let percent = total_bytes.map(|total| {
-                if total == 0 {
-                    0.0
-                } else {
-                    (downloaded as f64 / total as f64) * 100.0
-                }
-            });
+        if !sources.is_empty() {
+            download_from_sources(
+                &http.settings,
+                &model_name,
+                &sources,
+                &cancel_requested,
+                |source| {
+                    transfer_model_file(
+                        &app,
+                        &http.client,
+                        &downloads,
+                        &model_name,
+                        source,
+                        &temp_path,
+                        &cancel_requested,
+                    )
+                },
+            )?;
+        } else if let Some(source) = &model_info.local_path {
+            copy_local_model_file(&app, &downloads, &model_name, source, &temp_path)?;
+        } else {
+            return Err("Model has no download source".into());
+        }
 
+        if expected_sha256.is_some() {
             emit_download_event(
                 &app,
                 DownloadEventPayload {
                     model_name: model_name.clone(),
-                    downloaded_bytes: downloaded,
-                    total_bytes,
-                    percent,
-                    status: "downloading",
+                    downloaded_bytes: {
+                        let map = downloads.inner.lock();
+                        map.get(&model_name)
+                            .map(|entry| entry.downloaded_bytes)
+                            .unwrap_or(0)
+                    },
+                    total_bytes: {
+                        let map = downloads.inner.lock();
+                        map.get(&model_name).and_then(|entry| entry.total_bytes)
+                    },
+                    percent: Some(100.0),
+                    status: "verifying",
                     error: None,
+                    ..Default::default()
                 },
             );
         }
 
-        file.flush()?;
-        file.sync_all()?;
-
-        if overwrite && model_path.exists() {
-            std::fs::remove_file(&model_path)?;
-        }
-
-        std::fs::rename(&temp_path, &model_path)?;
-
-        Ok(())
+        install_downloaded_file(
+            &temp_path,
+            &model_path,
+            expected_sha256.as_deref(),
+            overwrite,
+            &cancel_requested,
+        )?;
+        Ok(expected_sha256)
     })();
 
     match result {
-        Ok(()) => {
+        Ok(verified_sha256) => {
             {
                 let mut map = downloads.inner.lock();
                 if let Some(entry) = map.get_mut(&model_name) {
@@ -393,6 +1250,10 @@ fn download_model_task(
                 }
             }
 
+            if let Some(sha256) = &verified_sha256 {
+                record_model_checksum(&app, &model_name, sha256);
+            }
+
             emit_download_event(
                 &app,
                 DownloadEventPayload {
@@ -410,6 +1271,8 @@ fn download_model_task(
                     percent: Some(100.0),
                     status: "completed",
                     error: None,
+                    verified: Some(verified_sha256.is_some()),
+                    ..Default::default()
                 },
             );
 
@@ -423,10 +1286,10 @@ fn download_model_task(
             };
 
             if should_reload {
-                match load_whisper_model_for(&app, &model_name) {
-                    Ok(ctx) => {
+                match whisper.load_engine_for(&app, &model_name) {
+                    Ok(engine) => {
                         let mut runtime = whisper.inner.lock();
-                        runtime.context = Some(ctx);
+                        runtime.engine = Some(engine);
                         runtime.current_model = Some(model_name.clone());
                         let _ = app.emit(
                             "active-model-changed",
@@ -441,18 +1304,47 @@ fn download_model_task(
                 }
             }
         }
+        Err(err) if err.is::<DownloadCancelled>() => {
+            let _ = remove_partial_download(&temp_path);
+
+            {
+                let mut map = downloads.inner.lock();
+                if let Some(entry) = map.get_mut(&model_name) {
+                    entry.status = DownloadStatus::Cancelled;
+                    entry.downloaded_bytes = 0;
+                    entry.total_bytes = None;
+                    entry.resume_offset = None;
+                    entry.error = None;
+                }
+            }
+
+            println!("Download of '{}' cancelled", model_name);
+
+            emit_download_event(
+                &app,
+                DownloadEventPayload {
+                    model_name: model_name.clone(),
+                    downloaded_bytes: 0,
+                    total_bytes: None,
+                    percent: None,
+                    status: "cancelled",
+                    error: None,
+                    ..Default::default()
+                },
+            );
+        }
         Err(err) => {
             let message = err.to_string();
+            let status = DownloadStatus::for_error(err.as_ref());
+            let verification_failed = status == DownloadStatus::VerificationFailed;
             {
                 let mut map = downloads.inner.lock();
                 if let Some(entry) = map.get_mut(&model_name) {
-                    entry.status = DownloadStatus::Failed;
+                    entry.status = status;
                     entry.error = Some(message.clone());
                 }
             }
 
-            let _ = std::fs::remove_file(&temp_path);
-
             emit_download_event(
                 &app,
                 DownloadEventPayload {
@@ -468,508 +1360,4092 @@ fn download_model_task(
                         map.get(&model_name).and_then(|entry| entry.total_bytes)
                     },
                     percent: None,
-                    status: "error",
+                    status: if verification_failed {
+                        "verification_failed"
+                    } else {
+                        "error"
+                    },
                     error: Some(message),
+                    ..Default::default()
                 },
             );
         }
     }
 }
 
-fn gather_model_statuses(
-    app: &AppHandle,
-    downloads: &DownloadManager,
-    whisper: &WhisperManager,
-) -> Vec<ModelStatus> {
-    let active_model = {
-        let runtime = whisper.inner.lock();
-        runtime.current_model.clone()
-    };
+// Check a finished download against `expected_sha256`, when there is one, and move it into
+// place as the model file
+fn install_downloaded_file(
+    temp_path: &Path,
+    model_path: &Path,
+    expected_sha256: Option<&str>,
+    overwrite: bool,
+    cancel_requested: &AtomicBool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(expected) = expected_sha256 {
+        let actual = compute_sha256(temp_path)?;
+        if cancel_requested.load(Ordering::Relaxed) {
+            return Err(DownloadCancelled.into());
+        }
+        if actual != expected {
+            // A corrupt partial file cannot be resumed, so start from scratch next time
+            let _ = remove_partial_download(temp_path);
+            return Err(ChecksumMismatchError {
+                expected: expected.to_string(),
+                actual,
+            }
+            .into());
+        }
+    }
 
-    let download_snapshot = downloads.inner.lock().clone();
+    if overwrite && model_path.exists() {
+        std::fs::remove_file(model_path)?;
+    }
 
-    get_available_models()
-        .into_iter()
-        .map(|model| {
-            let model_name = model.name.to_string();
-            let record = download_snapshot.get(&model_name);
-            let is_downloaded = model_exists_for(app, &model_name);
-            let is_downloading = record
-                .map(|entry| entry.status == DownloadStatus::Downloading)
-                .unwrap_or(false);
-            let is_active = active_model
-                .as_ref()
-                .map(|current| current == &model_name)
-                .unwrap_or(false);
+    std::fs::rename(temp_path, model_path)?;
+    // Only the validator is left of the partial download
+    let _ = remove_partial_download(temp_path);
+    Ok(())
+}
 
-            let (downloaded_bytes, total_bytes) = if let Some(entry) = record {
-                (entry.downloaded_bytes, entry.total_bytes)
-            } else if is_downloaded {
-                match std::fs::metadata(get_model_path_for(app, &model_name)) {
-                    Ok(meta) => {
-                        let len = meta.len();
-                        (len, Some(len))
+// Try each source in order, retrying transient failures before moving on to the next one.
+// The partial file is kept between sources since every mirror serves the same bytes, and the
+// checksum check afterwards catches any that do not.
+fn download_from_sources(
+    settings: &NetworkSettings,
+    model_name: &str,
+    sources: &[DownloadSource],
+    cancel_requested: &AtomicBool,
+    mut transfer: impl FnMut(&DownloadSource) -> Result<(), Box<dyn std::error::Error>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut last_error: Option<Box<dyn std::error::Error>> = None;
+    for source in sources {
+        if let Some(err) = &last_error {
+            eprintln!(
+                "Falling back to {} for '{}' after error: {}",
+                source.url, model_name, err
+            );
+        }
+
+        // Keep any partial file around: each attempt resumes from where the last one stopped
+        let max_attempts = settings.max_retries + 1;
+        let mut attempt = 1;
+        let result = loop {
+            match transfer(source) {
+                Ok(()) => break Ok(()),
+                Err(err)
+                    if attempt < max_attempts
+                        && !err.is::<HttpStatusError>()
+                        && !err.is::<DownloadCancelled>()
+                        && !err.is::<InsufficientDiskSpaceError>() =>
+                {
+                    eprintln!(
+                        "Download of '{}' interrupted (attempt {}/{}): {}",
+                        model_name, attempt, max_attempts, err
+                    );
+                    if !sleep_unless_cancelled(settings.retry_delay(attempt), cancel_requested) {
+                        return Err(DownloadCancelled.into());
                     }
-                    Err(_) => (0, None),
+                    attempt += 1;
                 }
-            } else {
-                (0, None)
-            };
+                Err(err) => break Err(err),
+            }
+        };
 
-            ModelStatus {
-                name: model_name,
-                size_mb: model.size_mb,
-                is_downloaded,
-                is_downloading,
-                is_active,
-                downloaded_bytes,
-                total_bytes,
-                error: record.and_then(|entry| entry.error.clone()),
+        match result {
+            Ok(()) => return Ok(()),
+            Err(err) if err.is::<DownloadCancelled>() || err.is::<InsufficientDiskSpaceError>() => {
+                return Err(err)
             }
-        })
-        .collect()
+            Err(err) => last_error = Some(err),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| "Model has no download source".into()))
 }
 
-// Get default model name
-const DEFAULT_MODEL: &str = "tiny.en-q5_1";
+// Wait out a retry backoff in short steps so a cancel takes effect right away. Returns false if
+// the download was cancelled meanwhile.
+fn sleep_unless_cancelled(delay: Duration, cancel_requested: &AtomicBool) -> bool {
+    let deadline = Instant::now() + delay;
+    loop {
+        if cancel_requested.load(Ordering::Relaxed) {
+            return false;
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return true;
+        }
+        std::thread::sleep(remaining.min(RETRY_CANCEL_POLL_INTERVAL));
+    }
+}
 
-// Get the model file path for a specific model
-fn get_model_path_for(app: &AppHandle, model_name: &str) -> PathBuf {
-    let models_dir = get_model_base_path(app).expect("Failed to get model base path");
+// Download `source` into `temp_path`, reporting progress as a model download event
+fn transfer_model_file(
+    app: &AppHandle,
+    client: &reqwest::blocking::Client,
+    downloads: &DownloadManager,
+    model_name: &str,
+    source: &DownloadSource,
+    temp_path: &Path,
+    cancel_requested: &AtomicBool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let update_record = |status: Option<DownloadStatus>, downloaded: u64, total: Option<u64>| {
+        let mut map = downloads.inner.lock();
+        if let Some(entry) = map.get_mut(model_name) {
+            if let Some(status) = status {
+                entry.status = status;
+            }
+            entry.downloaded_bytes = downloaded;
+            entry.total_bytes = total;
+        }
+    };
+    let percent_of = |downloaded: u64, total: Option<u64>| {
+        total.map(|total| {
+            if total == 0 {
+                0.0
+            } else {
+                (downloaded as f64 / total as f64) * 100.0
+            }
+        })
+    };
 
-    // Find the model info
-    let model_info = get_available_models()
-        .into_iter()
-        .find(|m| m.name == model_name)
-        .expect("Unknown model name");
+    let mut throttle = ProgressThrottle::new(Instant::now(), 0);
+    fetch_to_partial_file(
+        client,
+        source,
+        temp_path,
+        cancel_requested,
+        |progress| match progress {
+            TransferProgress::Started {
+                resume_offset,
+                total_bytes,
+            } => {
+                {
+                    let mut map = downloads.inner.lock();
+                    if let Some(entry) = map.get_mut(model_name) {
+                        entry.status = if resume_offset > 0 {
+                            DownloadStatus::Resuming
+                        } else {
+                            DownloadStatus::Downloading
+                        };
+                        entry.downloaded_bytes = resume_offset;
+                        entry.total_bytes = total_bytes;
+                        entry.resume_offset = (resume_offset > 0).then_some(resume_offset);
+                        entry.error = None;
+                    }
+                }
+                throttle = ProgressThrottle::new(Instant::now(), resume_offset);
+
+                emit_download_event(
+                    app,
+                    DownloadEventPayload {
+                        model_name: model_name.to_string(),
+                        downloaded_bytes: resume_offset,
+                        total_bytes,
+                        percent: percent_of(resume_offset, total_bytes),
+                        status: if resume_offset > 0 {
+                            "resuming"
+                        } else {
+                            "started"
+                        },
+                        error: None,
+                        resume_offset: (resume_offset > 0).then_some(resume_offset),
+                        ..Default::default()
+                    },
+                );
+            }
+            TransferProgress::Received {
+                downloaded,
+                total_bytes,
+            } => {
+                let now = Instant::now();
+                let percent = percent_of(downloaded, total_bytes);
+                throttle.record(now, downloaded);
+                if !throttle.should_emit(now, percent) {
+                    return;
+                }
+                update_record(Some(DownloadStatus::Downloading), downloaded, total_bytes);
+
+                let bytes_per_second = throttle.bytes_per_second();
+                emit_download_event(
+                    app,
+                    DownloadEventPayload {
+                        model_name: model_name.to_string(),
+                        downloaded_bytes: downloaded,
+                        total_bytes,
+                        percent,
+                        status: "downloading",
+                        error: None,
+                        bytes_per_second,
+                        eta_seconds: estimate_eta_seconds(
+                            downloaded,
+                            total_bytes,
+                            bytes_per_second,
+                        ),
+                        ..Default::default()
+                    },
+                );
+            }
+            TransferProgress::Finished {
+                downloaded,
+                total_bytes,
+            } => update_record(None, downloaded, total_bytes),
+        },
+    )
+}
 
-    models_dir.join(model_info.filename)
+// Reported by `fetch_to_partial_file` as the transfer goes along
+enum TransferProgress {
+    // The server accepted the request; the first `resume_offset` bytes of the partial file
+    // are kept
+    Started {
+        resume_offset: u64,
+        total_bytes: Option<u64>,
+    },
+    Received {
+        downloaded: u64,
+        total_bytes: Option<u64>,
+    },
+    // The body ended, possibly early; see the returned result
+    Finished {
+        downloaded: u64,
+        total_bytes: Option<u64>,
+    },
 }
 
-fn get_model_base_path(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|_| "Failed to get app data dir".to_string())?;
-    let models_dir = app_data_dir.join("models");
-    std::fs::create_dir_all(&models_dir)
-        .map_err(|e| format!("Failed to create models directory: {}", e))?;
-    Ok(models_dir)
+// Where the ETag or Last-Modified of the remote file behind a partial download is kept, so a
+// resume can tell whether the file changed in between
+fn get_validator_path_for(temp_path: &Path) -> PathBuf {
+    temp_path.with_extension("validator")
 }
 
-// Check if a specific model exists
-fn model_exists_for(app: &AppHandle, model_name: &str) -> bool {
-    get_model_path_for(app, model_name).exists()
+fn remove_partial_download(temp_path: &Path) -> std::io::Result<()> {
+    let validator_path = get_validator_path_for(temp_path);
+    if validator_path.exists() {
+        std::fs::remove_file(validator_path)?;
+    }
+    if temp_path.exists() {
+        std::fs::remove_file(temp_path)?;
+    }
+    Ok(())
 }
 
-// Transcribe audio using Whisper model
-fn transcribe_audio(
-    ctx: &mut WhisperContext,
-    audio_data: &[f32],
-    model_name: &str,
-) -> Result<String, Box<dyn std::error::Error>> {
-    if audio_data.is_empty() {
-        return Ok(String::new());
+// A strong ETag, or failing that Last-Modified; weak ETags are not allowed in `If-Range`
+fn response_validator(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    header(reqwest::header::ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header(reqwest::header::LAST_MODIFIED))
+        .map(str::to_string)
+}
+
+// Redirects `send_download_request` follows before giving up
+const MAX_DOWNLOAD_REDIRECTS: usize = 10;
+
+// GET `source`, following redirects by hand so the mirror's auth header is only sent to the
+// mirror itself. reqwest drops `Authorization` on cross-host redirects but would forward a
+// custom header name to wherever the mirror points. `client` must not follow redirects.
+fn send_download_request(
+    client: &reqwest::blocking::Client,
+    source: &DownloadSource,
+    headers: reqwest::header::HeaderMap,
+) -> Result<reqwest::blocking::Response, Box<dyn std::error::Error>> {
+    let origin = reqwest::Url::parse(&source.url)?.origin();
+    let mut url = reqwest::Url::parse(&source.url)?;
+    for _ in 0..=MAX_DOWNLOAD_REDIRECTS {
+        let mut request = client.get(url.clone()).headers(headers.clone());
+        if let Some((name, value)) = &source.auth_header {
+            if url.origin() == origin {
+                request = request.header(name.as_str(), value.as_str());
+            }
+        }
+        let response = request.send()?;
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|value| value.to_str().ok());
+        match location {
+            Some(location) if response.status().is_redirection() => url = url.join(location)?,
+            _ => return Ok(response),
+        }
     }
+    Err(format!("Too many redirects for {}", source.url).into())
+}
 
-    // Skip transcription for very short audio (< 0.3s at 16kHz)
-    if audio_data.len() < 4800 {
-        println!(
-            "Audio too short ({} samples), skipping transcription",
-            audio_data.len()
+// Stream `source` into `temp_path`, continuing from the end of an existing partial file with a
+// `Range` request. The validator saved with the partial file goes along as `If-Range`, so the
+// server sends the whole file again if it changed since; a server that ignores the range is
+// handled the same way.
+fn fetch_to_partial_file(
+    client: &reqwest::blocking::Client,
+    source: &DownloadSource,
+    temp_path: &Path,
+    cancel_requested: &AtomicBool,
+    mut on_progress: impl FnMut(TransferProgress),
+) -> Result<(), Box<dyn std::error::Error>> {
+    let existing_bytes = std::fs::metadata(temp_path)
+        .map(|meta| meta.len())
+        .unwrap_or(0);
+    let validator_path = get_validator_path_for(temp_path);
+    let validator = if existing_bytes > 0 {
+        std::fs::read_to_string(&validator_path).ok()
+    } else {
+        None
+    };
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    if existing_bytes > 0 {
+        headers.insert(
+            reqwest::header::RANGE,
+            format!("bytes={}-", existing_bytes).parse()?,
         );
-        return Ok(String::new());
+        if let Some(validator) = &validator {
+            headers.insert(reqwest::header::IF_RANGE, validator.parse()?);
+        }
+    }
+    let mut response = send_download_request(client, source, headers)?;
+    let status = response.status();
+
+    if existing_bytes > 0 && status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        // The partial file is either already complete or does not belong to this remote file
+        if parse_content_range_total(response.headers()) == Some(existing_bytes) {
+            on_progress(TransferProgress::Finished {
+                downloaded: existing_bytes,
+                total_bytes: Some(existing_bytes),
+            });
+            return Ok(());
+        }
+        remove_partial_download(temp_path)?;
+        return Err("Partial download does not match the remote file; restarting".into());
     }
 
-    println!("Starting transcription of {} samples...", audio_data.len());
+    if !status.is_success() {
+        return Err(Box::new(HttpStatusError(status)));
+    }
 
-    // Create transcription parameters optimized for speed
-    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
-    // Only set language to English for English-only models (.en variants)
-    // Multilingual models will auto-detect the language
-    if model_name.contains(".en") {
-        params.set_language(Some("en"));
-        println!("Using English-only model - language set to 'en'");
+    let resume_offset = if status == reqwest::StatusCode::PARTIAL_CONTENT {
+        existing_bytes
+    } else {
+        0
+    };
+    let total_bytes = if resume_offset > 0 {
+        parse_content_range_total(response.headers())
+            .or_else(|| response.content_length().map(|len| len + resume_offset))
     } else {
-        println!("Using multilingual model - language auto-detection enabled");
+        response.content_length()
+    };
+
+    if let (Some(total), Some(dir)) = (total_bytes, temp_path.parent()) {
+        ensure_disk_space(dir, total.saturating_sub(resume_offset))?;
     }
-    params.set_print_progress(false);
-    params.set_print_realtime(false);
-    params.set_print_timestamps(false);
-    // Use half of available CPU threads (leave room for other processes)
-    let n_threads = (std::thread::available_parallelism()
-        .map(|n| n.get())
-        .unwrap_or(4)
-        / 2)
-    .max(1);
-    params.set_n_threads(n_threads as i32);
 
-    // Run transcription
-    let mut state = ctx
-        .create_state()
-        .map_err(|e| format!("Failed to create state: {}", e))?;
-    state
-        .full(params, audio_data)
-        .map_err(|e| format!("Failed to run transcription: {}", e))?;
+    on_progress(TransferProgress::Started {
+        resume_offset,
+        total_bytes,
+    });
 
-    // Get the transcribed text from all segments using iterator
-    let mut transcription = String::new();
+    let mut file = if resume_offset > 0 {
+        std::fs::OpenOptions::new().append(true).open(temp_path)?
+    } else {
+        // Starting over, so remember which version of the remote file the bytes belong to
+        match response_validator(response.headers()) {
+            Some(validator) => std::fs::write(&validator_path, validator)?,
+            None if validator_path.exists() => std::fs::remove_file(&validator_path)?,
+            None => {}
+        }
+        std::fs::File::create(temp_path)?
+    };
+    let mut buffer = [0u8; 1024 * 64];
+    let mut downloaded = resume_offset;
 
-    for segment in state.as_iter() {
-        if let Ok(text) = segment.to_str() {
-            transcription.push_str(text);
+    loop {
+        if cancel_requested.load(Ordering::Relaxed) {
+            return Err(DownloadCancelled.into());
+        }
+        let bytes_read = response.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
         }
+        file.write_all(&buffer[..bytes_read])?;
+        downloaded += bytes_read as u64;
+        on_progress(TransferProgress::Received {
+            downloaded,
+            total_bytes,
+        });
     }
 
-    let trimmed = transcription.trim().to_string();
-    println!("Transcription complete: \"{}\"", trimmed);
+    file.flush()?;
+    file.sync_all()?;
+    on_progress(TransferProgress::Finished {
+        downloaded,
+        total_bytes,
+    });
+
+    // A dropped connection can end the body early without an error
+    if let Some(total) = total_bytes {
+        if downloaded < total {
+            return Err(
+                format!("Connection closed after {} of {} bytes", downloaded, total).into(),
+            );
+        }
+    }
 
-    Ok(trimmed)
+    Ok(())
 }
 
-// Load Whisper model (with Metal GPU support)
-fn load_whisper_model_for(
+// Custom catalog entries may point at a file on this machine instead of a URL
+fn copy_local_model_file(
     app: &AppHandle,
+    downloads: &DownloadManager,
     model_name: &str,
-) -> Result<WhisperContext, Box<dyn std::error::Error>> {
-    let model_path = get_model_path_for(app, model_name);
-
-    if !model_path.exists() {
-        return Err("Model not found. Please download the model first.".into());
-    }
+    source: &Path,
+    temp_path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let total_bytes = std::fs::metadata(source)?.len();
 
-    println!("Loading Whisper model from: {:?}", model_path);
+    {
+        let mut map = downloads.inner.lock();
+        if let Some(entry) = map.get_mut(model_name) {
+            entry.status = DownloadStatus::Downloading;
+            entry.downloaded_bytes = 0;
+            entry.total_bytes = Some(total_bytes);
+            entry.resume_offset = None;
+            entry.error = None;
+        }
+    }
+
+    emit_download_event(
+        app,
+        DownloadEventPayload {
+            model_name: model_name.to_string(),
+            total_bytes: Some(total_bytes),
+            percent: Some(0.0),
+            status: "started",
+            ..Default::default()
+        },
+    );
+
+    let copied = std::fs::copy(source, temp_path)?;
 
-    // WhisperContext will automatically use Metal GPU if compiled with metal feature
-    let params = WhisperContextParameters::default();
-    let ctx =
-        WhisperContext::new_with_params(model_path.to_str().ok_or("Invalid model path")?, params)?;
+    {
+        let mut map = downloads.inner.lock();
+        if let Some(entry) = map.get_mut(model_name) {
+            entry.downloaded_bytes = copied;
+        }
+    }
 
-    println!("Whisper model loaded successfully (Metal GPU enabled via feature flag)");
-    Ok(ctx)
+    Ok(())
 }
 
-// Audio recording state - stores the stream and buffers audio data
-struct AudioRecorder {
-    stream: Option<cpal::Stream>,
-    buffer: Arc<Mutex<Vec<f32>>>,
-    sample_rate: u32,
-    temp_buffer: Arc<Mutex<Vec<f32>>>, // Temporary buffer for incoming 48kHz samples
+// Hugging Face reports the SHA-256 of LFS-hosted files in the `X-Linked-Etag` header of the
+// resolve redirect, so the request must not follow it
+fn fetch_advertised_sha256(
+    client: &reqwest::blocking::Client,
+    source: &DownloadSource,
+) -> Option<String> {
+    let mut request = client.head(&source.url);
+    if let Some((name, value)) = &source.auth_header {
+        request = request.header(name.as_str(), value.as_str());
+    }
+    let response = request.send().ok()?;
+    let headers = response.headers();
+    let etag = headers
+        .get("x-linked-etag")
+        .or_else(|| headers.get(reqwest::header::ETAG))?
+        .to_str()
+        .ok()?;
+    let digest = etag
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .to_ascii_lowercase();
+    is_sha256_hex(&digest).then_some(digest)
 }
 
-// Safety: AudioRecorder is only accessed from the main thread via parking_lot::Mutex
-unsafe impl Send for AudioRecorder {}
-unsafe impl Sync for AudioRecorder {}
+// Fail with `InsufficientDiskSpaceError` unless `required` bytes plus headroom fit on the volume
+// containing `dir`. Skipped when free space cannot be determined.
+fn ensure_disk_space(dir: &Path, required: u64) -> Result<(), Box<dyn std::error::Error>> {
+    match disk_space(dir) {
+        Ok(space) => {
+            if space.available < required.saturating_add(DISK_SPACE_HEADROOM_BYTES) {
+                return Err(Box::new(InsufficientDiskSpaceError {
+                    required,
+                    available: space.available,
+                }));
+            }
+        }
+        Err(err) => eprintln!("Could not determine free space in {:?}: {}", dir, err),
+    }
+    Ok(())
+}
 
-impl AudioRecorder {
-    fn new() -> Self {
-        Self {
-            stream: None,
-            buffer: Arc::new(Mutex::new(Vec::new())),
-            sample_rate: 0,
-            temp_buffer: Arc::new(Mutex::new(Vec::new())),
+struct DiskSpace {
+    available: u64,
+    total: u64,
+}
+
+#[cfg(unix)]
+fn disk_space(path: &Path) -> std::io::Result<DiskSpace> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let block_size = stat.f_frsize as u64;
+    Ok(DiskSpace {
+        available: stat.f_bavail as u64 * block_size,
+        total: stat.f_blocks as u64 * block_size,
+    })
+}
+
+#[cfg(not(unix))]
+fn disk_space(_path: &Path) -> std::io::Result<DiskSpace> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "free space lookup is not supported on this platform",
+    ))
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+// The checksum a download is verified against. Only custom entries fall back to the digest the
+// server advertises (`advertised` is not called otherwise); built-ins rely on their pinned value.
+fn expected_model_sha256(
+    model: &ModelInfo,
+    advertised: impl FnOnce() -> Option<String>,
+) -> Option<String> {
+    match &model.sha256 {
+        Some(sha256) => Some(sha256.clone()),
+        None if model.custom => advertised(),
+        None => None,
+    }
+}
+
+fn is_sha256_hex(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn compute_sha256(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let bytes_read = file.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
         }
+        hasher.update(&buffer[..bytes_read]);
     }
+    Ok(format!("{:x}", hasher.finalize()))
+}
 
-    fn start(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        if self.stream.is_none() {
-            // Clear previous buffers
-            self.buffer.lock().clear();
-            self.temp_buffer.lock().clear();
+// Total length from a `Content-Range: bytes 0-99/1234` (or `bytes */1234`) header
+fn parse_content_range_total(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+    headers
+        .get(reqwest::header::CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .rsplit('/')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
 
-            // Get the default audio host and input device
-            let host = cpal::default_host();
-            let device = host
-                .default_input_device()
-                .ok_or("No input device available")?;
+fn gather_model_statuses(
+    app: &AppHandle,
+    downloads: &DownloadManager,
+    whisper: &WhisperManager,
+) -> Vec<ModelStatus> {
+    let active_model = {
+        let runtime = whisper.inner.lock();
+        runtime.current_model.clone()
+    };
 
-            // Try to get 16kHz config (Whisper requirement)
-            let config = match device.supported_input_configs() {
-                Ok(configs) => {
-                    // Try to find a 16kHz mono config
-                    let mut found_16khz = None;
-                    for config in configs {
-                        if config.min_sample_rate().0 <= 16000
-                            && config.max_sample_rate().0 >= 16000
-                        {
-                            // Found a config that supports 16kHz
-                            found_16khz = Some(config.with_sample_rate(cpal::SampleRate(16000)));
-                            break;
-                        }
-                    }
+    let queue_order: Vec<String> = downloads
+        .queue
+        .lock()
+        .pending
+        .iter()
+        .map(|item| item.model_name.clone())
+        .collect();
+    let download_snapshot = downloads.inner.lock().clone();
+    let verified_models = load_app_config(app)
+        .map(|config| config.model_checksums)
+        .unwrap_or_default();
 
-                    match found_16khz {
-                        Some(cfg) => cfg,
-                        None => {
-                            // Fallback to default config if 16kHz not supported
-                            println!("16kHz not supported, using default config");
-                            device.default_input_config()?
-                        }
+    get_available_models(app)
+        .into_iter()
+        .map(|model| {
+            let model_name = model.name.clone();
+            let record = download_snapshot.get(&model_name);
+            let is_downloaded = model_exists_for(app, &model_name);
+            let is_downloading = record
+                .map(|entry| entry.status.is_in_progress())
+                .unwrap_or(false);
+            let is_queued = record
+                .map(|entry| entry.status == DownloadStatus::Queued)
+                .unwrap_or(false);
+            let is_paused = record
+                .map(|entry| entry.status == DownloadStatus::Paused)
+                .unwrap_or(false);
+            let queue_position = queue_order.iter().position(|name| name == &model_name);
+            let is_active = active_model
+                .as_ref()
+                .map(|current| current == &model_name)
+                .unwrap_or(false);
+            let is_verified = is_downloaded && verified_models.contains_key(&model_name);
+
+            let (downloaded_bytes, total_bytes) = if let Some(entry) = record {
+                (entry.downloaded_bytes, entry.total_bytes)
+            } else if is_downloaded {
+                match get_model_path_for(app, &model_name)
+                    .and_then(|path| std::fs::metadata(path).map_err(|e| e.to_string()))
+                {
+                    Ok(meta) => {
+                        let len = meta.len();
+                        (len, Some(len))
                     }
+                    Err(_) => (0, None),
                 }
-                Err(_) => device.default_input_config()?,
+            } else {
+                (0, None)
+            };
+
+            let resume_offset = match record {
+                Some(entry) if entry.status.is_transferring() => entry.resume_offset,
+                _ => get_model_path_for(app, &model_name)
+                    .ok()
+                    .and_then(|path| std::fs::metadata(get_temp_path_for(&path)).ok())
+                    .map(|meta| meta.len())
+                    .filter(|len| *len > 0),
             };
 
-            self.sample_rate = config.sample_rate().0;
-            println!("Starting audio capture with config: {:?}", config);
+            ModelStatus {
+                name: model_name,
+                size_mb: model.size_mb,
+                is_custom: model.custom,
+                is_downloaded,
+                is_downloading,
+                is_queued,
+                is_paused,
+                queue_position,
+                is_active,
+                is_verified,
+                downloaded_bytes,
+                total_bytes,
+                resume_offset,
+                error: record.and_then(|entry| entry.error.clone()),
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone, Serialize)]
+struct StorageEntry {
+    file_name: String,
+    size_bytes: u64,
+    // For partial downloads of a known model: purging them discards the resumable progress
+    model_name: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct StorageReport {
+    // `.download` files not belonging to a queued or running download, and leftover
+    // `.migrating` copies from an interrupted folder move
+    stale_temp_files: Vec<StorageEntry>,
+    // Model files that match no model in the catalog, e.g. renamed or removed models
+    orphan_files: Vec<StorageEntry>,
+    reclaimable_bytes: u64,
+}
+
+// Files this app can have written to the models folder: ggml models plus download and migration
+// partials. Anything else may belong to the user, since the folder can be any directory.
+fn is_app_model_file(file_name: &str) -> bool {
+    [".bin", ".download", ".validator", ".migrating"]
+        .iter()
+        .any(|extension| file_name.ends_with(extension))
+}
+
+fn scan_models_storage(
+    app: &AppHandle,
+    downloads: &DownloadManager,
+) -> Result<StorageReport, String> {
+    let models_dir = get_model_base_path(app)?;
+    let models = get_available_models(app);
+    let busy: HashSet<String> = downloads
+        .inner
+        .lock()
+        .iter()
+        .filter(|(_, entry)| entry.status.is_in_progress())
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    let mut model_files = HashSet::new();
+    let mut temp_files = HashMap::new();
+    for model in &models {
+        model_files.insert(model.filename.clone());
+        let temp_path = get_temp_path_for(Path::new(&model.filename));
+        for path in [get_validator_path_for(&temp_path), temp_path] {
+            temp_files.insert(path.to_string_lossy().into_owned(), model.name.clone());
+        }
+    }
+
+    let mut report = StorageReport {
+        stale_temp_files: Vec::new(),
+        orphan_files: Vec::new(),
+        reclaimable_bytes: 0,
+    };
+
+    let entries = std::fs::read_dir(&models_dir)
+        .map_err(|e| format!("Failed to read models folder: {}", e))?;
+    for entry in entries.flatten() {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        // Leave directories, OS metadata such as `.DS_Store` and files we did not write alone
+        if !meta.is_file()
+            || file_name.starts_with('.')
+            || !is_app_model_file(&file_name)
+            || model_files.contains(&file_name)
+        {
+            continue;
+        }
+
+        let model_name = temp_files.get(&file_name).cloned();
+        if model_name.as_ref().is_some_and(|name| busy.contains(name)) {
+            continue;
+        }
+
+        let storage_entry = StorageEntry {
+            file_name: file_name.clone(),
+            size_bytes: meta.len(),
+            model_name,
+        };
+        report.reclaimable_bytes += meta.len();
+        if file_name.ends_with(".bin") {
+            report.orphan_files.push(storage_entry);
+        } else {
+            report.stale_temp_files.push(storage_entry);
+        }
+    }
+
+    report
+        .stale_temp_files
+        .sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    report
+        .orphan_files
+        .sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    Ok(report)
+}
+
+// Get default model name
+const DEFAULT_MODEL: &str = "tiny.en-q5_1";
+
+// Get the model file path for a specific model
+fn get_model_path_for(app: &AppHandle, model_name: &str) -> Result<PathBuf, String> {
+    let models_dir = get_model_base_path(app)?;
+
+    // Find the model info
+    let model_info =
+        find_model_info(app, model_name).ok_or_else(|| "Unknown model name".to_string())?;
+
+    Ok(models_dir.join(model_info.filename))
+}
+
+// Partial downloads live next to the model file until they are complete
+fn get_temp_path_for(model_path: &Path) -> PathBuf {
+    model_path.with_extension("download")
+}
+
+fn get_model_base_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let custom_dir = app
+        .try_state::<ModelsDirectory>()
+        .and_then(|dir| dir.custom.lock().clone());
+    if let Some(models_dir) = custom_dir {
+        match std::fs::create_dir_all(&models_dir) {
+            Ok(()) => return Ok(models_dir),
+            // e.g. an external volume that is not mounted right now
+            Err(e) => eprintln!(
+                "Custom models directory {:?} unavailable, using default: {}",
+                models_dir, e
+            ),
+        }
+    }
+
+    let models_dir = get_default_models_path(app)?;
+    std::fs::create_dir_all(&models_dir)
+        .map_err(|e| format!("Failed to create models directory: {}", e))?;
+    Ok(models_dir)
+}
+
+fn get_default_models_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|_| "Failed to get app data dir".to_string())?;
+    Ok(app_data_dir.join("models"))
+}
+
+fn ensure_writable_dir(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+    let probe = dir.join(".sotto-write-test");
+    std::fs::write(&probe, b"ok")
+        .map_err(|e| format!("{} is not writable: {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+// A file moved into the new models folder; renamed files are moved back on rollback, copied
+// files are deleted (their source is only removed once everything succeeded)
+struct MigratedFile {
+    source: PathBuf,
+    destination: PathBuf,
+    renamed: bool,
+}
+
+// Move the catalog's model files (including partial downloads) from `source_dir` to
+// `target_dir`; anything else in the folder is left where it is. Renames when both are on the
+// same volume and copies otherwise. On error everything moved so far is put back.
+fn migrate_models_dir(
+    app: &AppHandle,
+    source_dir: &Path,
+    target_dir: &Path,
+) -> Result<Vec<MigratedFile>, String> {
+    // The new folder may hold other model files, but nothing of the user's that we could mix
+    // up with ours
+    let target_entries = std::fs::read_dir(target_dir)
+        .map_err(|e| format!("Failed to read the new folder: {}", e))?;
+    for entry in target_entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if !file_name.starts_with('.') && !is_app_model_file(&file_name) {
+            return Err(
+                "The new folder must be empty or contain only Whisper model files.".to_string(),
+            );
+        }
+    }
+
+    let mut owned_files = HashSet::new();
+    for model in get_available_models(app) {
+        let model_path = PathBuf::from(&model.filename);
+        let temp_path = get_temp_path_for(&model_path);
+        owned_files.insert(get_validator_path_for(&temp_path).into_os_string());
+        owned_files.insert(temp_path.into_os_string());
+        owned_files.insert(model_path.into_os_string());
+    }
+
+    let mut files = Vec::new();
+    let entries = std::fs::read_dir(source_dir)
+        .map_err(|e| format!("Failed to read models folder: {}", e))?;
+    for entry in entries.flatten() {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        let file_name = entry.file_name();
+        if meta.is_file() && owned_files.contains(&file_name) {
+            files.push((file_name, meta.len()));
+        }
+    }
+
+    for (file_name, _) in &files {
+        if target_dir.join(file_name).exists() {
+            return Err(format!(
+                "{} already exists in the new folder.",
+                file_name.to_string_lossy()
+            ));
+        }
+    }
+
+    let bytes_total: u64 = files.iter().map(|(_, len)| len).sum();
+    // Renames within one volume need no extra space
+    if !same_volume(source_dir, target_dir) {
+        ensure_disk_space(target_dir, bytes_total).map_err(|e| e.to_string())?;
+    }
+
+    let mut progress = MigrationEventPayload {
+        status: "started",
+        files_total: files.len(),
+        bytes_total,
+        ..Default::default()
+    };
+    let _ = app.emit("models-migration-progress", progress.clone());
+
+    let mut migrated: Vec<MigratedFile> = Vec::new();
+    let result: Result<(), String> = (|| {
+        let mut last_emit = Instant::now();
+        for (file_name, len) in &files {
+            let source = source_dir.join(file_name);
+            let destination = target_dir.join(file_name);
+            progress.status = "moving";
+            progress.file_name = Some(file_name.to_string_lossy().into_owned());
+
+            if std::fs::rename(&source, &destination).is_ok() {
+                migrated.push(MigratedFile {
+                    source,
+                    destination,
+                    renamed: true,
+                });
+            } else {
+                // Copy under a temporary name so an interrupted copy never looks like a model
+                let partial = destination.with_extension("migrating");
+                let copy_result = copy_file_with_progress(&source, &partial, |copied| {
+                    if last_emit.elapsed() >= PROGRESS_EMIT_INTERVAL {
+                        last_emit = Instant::now();
+                        let mut update = progress.clone();
+                        update.bytes_done += copied;
+                        let _ = app.emit("models-migration-progress", update);
+                    }
+                })
+                .and_then(|()| std::fs::rename(&partial, &destination));
+                if let Err(e) = copy_result {
+                    let _ = std::fs::remove_file(&partial);
+                    return Err(format!(
+                        "Failed to move {}: {}",
+                        file_name.to_string_lossy(),
+                        e
+                    ));
+                }
+                migrated.push(MigratedFile {
+                    source,
+                    destination,
+                    renamed: false,
+                });
+            }
+
+            progress.files_done += 1;
+            progress.bytes_done += len;
+            let _ = app.emit("models-migration-progress", progress.clone());
+        }
+        Ok(())
+    })();
+
+    if let Err(err) = result {
+        rollback_migration(&migrated);
+        let _ = app.emit(
+            "models-migration-progress",
+            MigrationEventPayload {
+                status: "rolled_back",
+                error: Some(err.clone()),
+                ..progress
+            },
+        );
+        return Err(err);
+    }
+    Ok(migrated)
+}
+
+fn rollback_migration(migrated: &[MigratedFile]) {
+    for file in migrated.iter().rev() {
+        let restored = if file.renamed {
+            std::fs::rename(&file.destination, &file.source)
+        } else {
+            std::fs::remove_file(&file.destination)
+        };
+        if let Err(err) = restored {
+            eprintln!("Failed to roll back {:?}: {}", file.destination, err);
+        }
+    }
+}
+
+fn copy_file_with_progress(
+    source: &Path,
+    destination: &Path,
+    mut on_progress: impl FnMut(u64),
+) -> std::io::Result<()> {
+    let mut reader = std::fs::File::open(source)?;
+    let mut writer = std::fs::File::create(destination)?;
+    let mut buffer = vec![0u8; 1024 * 1024];
+    let mut copied = 0u64;
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..read])?;
+        copied += read as u64;
+        on_progress(copied);
+    }
+    writer.sync_all()
+}
+
+#[cfg(unix)]
+fn same_volume(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (std::fs::metadata(a), std::fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn same_volume(_a: &Path, _b: &Path) -> bool {
+    false
+}
+
+// Check if a specific model exists
+fn model_exists_for(app: &AppHandle, model_name: &str) -> bool {
+    get_model_path_for(app, model_name).is_ok_and(|path| path.exists())
+}
+
+// A transcribed span of the clip; timestamps are milliseconds from its start
+#[derive(Clone, Debug, Serialize)]
+struct TranscriptSegment {
+    text: String,
+    #[serde(rename = "startMs")]
+    start_ms: i64,
+    #[serde(rename = "endMs")]
+    end_ms: i64,
+    // Mean probability of the segment's text tokens, a rough confidence measure
+    #[serde(rename = "avgTokenProbability")]
+    avg_token_probability: Option<f32>,
+}
+
+// Below this the frontend warns that the language may have been misdetected
+const LOW_LANGUAGE_CONFIDENCE: f32 = 0.5;
+
+#[derive(Clone, Debug, Default, Serialize)]
+struct TranscriptionResult {
+    text: String,
+    segments: Vec<TranscriptSegment>,
+    // Language the text was decoded as; detected unless fixed in settings
+    language: Option<String>,
+    // Only set when the language was auto-detected
+    #[serde(rename = "languageProbability")]
+    language_probability: Option<f32>,
+    #[serde(rename = "lowLanguageConfidence")]
+    low_language_confidence: bool,
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+struct EngineCapabilities {
+    multilingual: bool,
+    translation: bool,
+    language_detection: bool,
+    // Rate of the mono f32 samples passed to `transcribe`
+    sample_rate: u32,
+}
+
+// Spoken language handling for multilingual models; English-only models always use "en"
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct LanguageSettings {
+    // whisper language code such as "de"; auto-detected when unset
+    language: Option<String>,
+    // Output English text whatever language is spoken
+    translate_to_english: bool,
+}
+
+// Text the decoder is primed with, used to bias it towards product names, identifiers and a
+// preferred spelling or style
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct PromptSettings {
+    initial_prompt: String,
+    vocabulary: Vec<String>,
+    // Overrides for specific applications, matched against the frontmost app (macOS only)
+    profiles: Vec<PromptProfile>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct PromptProfile {
+    // Application name as shown in the menu bar, e.g. "Code" or "Slack"
+    app_name: String,
+    // Replaces the global prompt when not empty
+    initial_prompt: String,
+    // Added to the global vocabulary
+    vocabulary: Vec<String>,
+}
+
+impl PromptSettings {
+    fn normalize(&mut self) -> Result<(), String> {
+        self.initial_prompt = self.initial_prompt.trim().to_string();
+        normalize_vocabulary(&mut self.vocabulary);
+
+        let mut seen = HashSet::new();
+        for profile in &mut self.profiles {
+            profile.app_name = profile.app_name.trim().to_string();
+            if profile.app_name.is_empty() {
+                return Err("Profiles need an application name.".to_string());
+            }
+            if !seen.insert(profile.app_name.to_lowercase()) {
+                return Err(format!(
+                    "There is already a profile for '{}'.",
+                    profile.app_name
+                ));
+            }
+            profile.initial_prompt = profile.initial_prompt.trim().to_string();
+            normalize_vocabulary(&mut profile.vocabulary);
+        }
+        Ok(())
+    }
+
+    fn build_prompt(&self, app_name: Option<&str>) -> Option<String> {
+        let profile = app_name.and_then(|name| {
+            self.profiles
+                .iter()
+                .find(|profile| profile.app_name.eq_ignore_ascii_case(name))
+        });
+
+        let prompt = profile
+            .map(|profile| profile.initial_prompt.as_str())
+            .filter(|prompt| !prompt.is_empty())
+            .unwrap_or(&self.initial_prompt);
+
+        let mut vocabulary: Vec<&str> = self.vocabulary.iter().map(String::as_str).collect();
+        if let Some(profile) = profile {
+            for word in &profile.vocabulary {
+                if !vocabulary.contains(&word.as_str()) {
+                    vocabulary.push(word);
+                }
+            }
+        }
+
+        // Whisper keeps only the end of long prompts, so the vocabulary goes last
+        let mut parts = Vec::new();
+        if !prompt.is_empty() {
+            parts.push(prompt.to_string());
+        }
+        if !vocabulary.is_empty() {
+            parts.push(format!("Vocabulary: {}.", vocabulary.join(", ")));
+        }
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+}
+
+fn normalize_vocabulary(vocabulary: &mut Vec<String>) {
+    let mut seen = HashSet::new();
+    vocabulary.retain_mut(|word| {
+        *word = word.trim().to_string();
+        !word.is_empty() && seen.insert(word.clone())
+    });
+}
+
+// The prompt for the next transcription, taking the frontmost application's profile into
+// account
+fn configured_initial_prompt(app: &AppHandle) -> Option<String> {
+    let prompts = load_app_config(app).ok()?.prompts;
+    let app_name = if prompts.profiles.is_empty() {
+        None
+    } else {
+        frontmost_app_name()
+    };
+    prompts.build_prompt(app_name.as_deref())
+}
+
+// Finds the frontmost application on a background thread, since `osascript` can take a moment
+// and the shortcut handler must not wait for it. Only started when a prompt profile could use
+// the answer.
+struct FrontmostAppLookup(std::thread::JoinHandle<Option<String>>);
+
+impl FrontmostAppLookup {
+    fn start(app: &AppHandle) -> Option<Self> {
+        let has_profiles =
+            load_app_config(app).is_ok_and(|config| !config.prompts.profiles.is_empty());
+        has_profiles.then(|| Self(std::thread::spawn(frontmost_app_name)))
+    }
+
+    fn finish(self) -> Option<String> {
+        self.0.join().ok().flatten()
+    }
+}
+
+// The prompt for a queued recording, using the profile of the app it was dictated into
+fn initial_prompt_for(app: &AppHandle, target_app: Option<FrontmostAppLookup>) -> Option<String> {
+    let prompts = load_app_config(app).ok()?.prompts;
+    let app_name = target_app.and_then(FrontmostAppLookup::finish);
+    prompts.build_prompt(app_name.as_deref())
+}
+
+#[cfg(target_os = "macos")]
+fn frontmost_app_name() -> Option<String> {
+    let output = std::process::Command::new("osascript")
+        .args([
+            "-e",
+            "tell application \"System Events\" to get name of first application process whose frontmost is true",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn frontmost_app_name() -> Option<String> {
+    None
+}
+
+#[derive(Clone, Serialize)]
+struct SupportedLanguage {
+    code: String,
+    name: String,
+}
+
+#[derive(Clone, Debug, Default)]
+struct TranscriptionOptions {
+    language: ModelLanguage,
+    // Fixed source language; `None` lets the engine detect it
+    source_language: Option<String>,
+    translate_to_english: bool,
+    // Text the decoder is conditioned on, e.g. what was already said earlier in the utterance
+    initial_prompt: Option<String>,
+    decoding: DecodingSettings,
+}
+
+// Speed vs accuracy trade-off; `Custom` uses the individual values of `DecodingSettings`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum DecodingPreset {
+    #[default]
+    Fast,
+    Balanced,
+    Accurate,
+    Custom,
+}
+
+const MAX_BEAM_SIZE: u32 = 8;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct DecodingSettings {
+    preset: DecodingPreset,
+    // 1 decodes greedily; larger values use beam search
+    beam_size: u32,
+    // Beam search patience; whisper.cpp's default when unset
+    patience: Option<f32>,
+    temperature: f32,
+    // Added to the temperature for each retry when decoding fails; 0 disables the fallback
+    temperature_increment: f32,
+    // Segments more likely than this to be silence are dropped
+    no_speech_threshold: f32,
+    // 0 uses half of the available cores
+    threads: usize,
+    suppress_blank: bool,
+    // Suppress non-speech tokens such as music notes and speaker labels
+    suppress_non_speech_tokens: bool,
+}
+
+impl Default for DecodingSettings {
+    fn default() -> Self {
+        Self::for_preset(DecodingPreset::Fast)
+    }
+}
+
+impl DecodingSettings {
+    fn for_preset(preset: DecodingPreset) -> Self {
+        let (beam_size, patience, threads) = match preset {
+            DecodingPreset::Fast | DecodingPreset::Custom => (1, None, 0),
+            DecodingPreset::Balanced => (3, Some(1.0), 0),
+            DecodingPreset::Accurate => (5, Some(1.0), available_threads()),
+        };
+        Self {
+            preset,
+            beam_size,
+            patience,
+            temperature: 0.0,
+            temperature_increment: 0.2,
+            no_speech_threshold: 0.6,
+            threads,
+            suppress_blank: true,
+            suppress_non_speech_tokens: false,
+        }
+    }
+
+    // The values to decode with: the preset's unless it is `Custom`
+    fn effective(&self) -> Self {
+        match self.preset {
+            DecodingPreset::Custom => self.clone(),
+            preset => Self::for_preset(preset),
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_BEAM_SIZE).contains(&self.beam_size) {
+            return Err(format!(
+                "Beam size must be between 1 and {}.",
+                MAX_BEAM_SIZE
+            ));
+        }
+        if let Some(patience) = self.patience {
+            if !(0.5..=3.0).contains(&patience) {
+                return Err("Patience must be between 0.5 and 3.".to_string());
+            }
+        }
+        if !(0.0..=1.0).contains(&self.temperature) {
+            return Err("Temperature must be between 0 and 1.".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.temperature_increment) {
+            return Err("Temperature increment must be between 0 and 1.".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.no_speech_threshold) {
+            return Err("No-speech threshold must be between 0 and 1.".to_string());
+        }
+        if self.threads > available_threads() {
+            return Err(format!(
+                "This machine has {} threads available.",
+                available_threads()
+            ));
+        }
+        Ok(())
+    }
+
+    fn thread_count(&self) -> usize {
+        if self.threads > 0 {
+            self.threads
+        } else {
+            // Use half of available CPU threads (leave room for other processes)
+            (available_threads() / 2).max(1)
+        }
+    }
+}
+
+fn available_threads() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+}
+
+// A local speech-to-text backend. whisper.cpp is the only one today; `WhisperRuntime` holds
+// whichever engine the selected model needs.
+trait TranscriptionEngine: Send {
+    fn load(model_path: &Path) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        Self: Sized;
+
+    fn capabilities(&self) -> EngineCapabilities;
+
+    fn transcribe(
+        &mut self,
+        samples: &[f32],
+        options: &TranscriptionOptions,
+    ) -> Result<TranscriptionResult, Box<dyn std::error::Error>>;
+}
+
+struct WhisperEngine {
+    ctx: WhisperContext,
+}
+
+impl TranscriptionEngine for WhisperEngine {
+    // Uses Metal on macOS when whisper-rs is built with the `metal` feature
+    fn load(model_path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let params = WhisperContextParameters::default();
+        let ctx = WhisperContext::new_with_params(
+            model_path.to_str().ok_or("Invalid model path")?,
+            params,
+        )?;
+        Ok(Self { ctx })
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        let multilingual = self.ctx.is_multilingual();
+        EngineCapabilities {
+            multilingual,
+            translation: multilingual,
+            language_detection: multilingual,
+            sample_rate: 16000,
+        }
+    }
+
+    fn transcribe(
+        &mut self,
+        samples: &[f32],
+        options: &TranscriptionOptions,
+    ) -> Result<TranscriptionResult, Box<dyn std::error::Error>> {
+        let decoding = &options.decoding;
+        let n_threads = decoding.thread_count();
+
+        let mut state = self
+            .ctx
+            .create_state()
+            .map_err(|e| format!("Failed to create state: {}", e))?;
+
+        let strategy = if decoding.beam_size > 1 {
+            SamplingStrategy::BeamSearch {
+                beam_size: decoding.beam_size as i32,
+                // whisper.cpp treats negative values as "use the default"
+                patience: decoding.patience.unwrap_or(-1.0),
+            }
+        } else {
+            SamplingStrategy::Greedy { best_of: 1 }
+        };
+        let mut params = FullParams::new(strategy);
+        let mut language_probability = None;
+        let language: Option<&str> = if options.language == ModelLanguage::English {
+            println!("Using English-only model - language set to 'en'");
+            Some("en")
+        } else {
+            params.set_translate(options.translate_to_english);
+            match options.source_language.as_deref() {
+                Some(code) => {
+                    println!("Using multilingual model - language set to '{}'", code);
+                    Some(code)
+                }
+                None => {
+                    // Detect up front rather than inside `full` so the probability is known
+                    match detect_language(&mut state, samples, n_threads) {
+                        Ok((code, probability)) => {
+                            println!("Detected language '{}' (p = {:.2})", code, probability);
+                            language_probability = Some(probability);
+                            Some(code)
+                        }
+                        Err(e) => {
+                            eprintln!("Language detection failed, leaving it to whisper: {}", e);
+                            None
+                        }
+                    }
+                }
+            }
+        };
+        // whisper.cpp defaults to "en"; "auto" is what enables its own detection
+        params.set_language(Some(language.unwrap_or("auto")));
+        if let Some(prompt) = &options.initial_prompt {
+            params.set_initial_prompt(prompt);
+        }
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        params.set_n_threads(n_threads as i32);
+        params.set_temperature(decoding.temperature);
+        params.set_temperature_inc(decoding.temperature_increment);
+        params.set_no_speech_thold(decoding.no_speech_threshold);
+        params.set_suppress_blank(decoding.suppress_blank);
+        params.set_suppress_nst(decoding.suppress_non_speech_tokens);
+
+        // Run transcription
+        state
+            .full(params, samples)
+            .map_err(|e| format!("Failed to run transcription: {}", e))?;
+
+        // whisper.cpp timestamps are in centiseconds
+        let segments: Vec<TranscriptSegment> = state
+            .as_iter()
+            .filter_map(|segment| {
+                let text = segment.to_str().ok()?.to_string();
+                let probabilities: Vec<f32> = (0..segment.n_tokens())
+                    .filter_map(|i| segment.get_token(i))
+                    .filter(|token| {
+                        // Skip special tokens such as `[_BEG_]` and timestamps
+                        !token.to_str().is_ok_and(|text| text.starts_with("[_"))
+                    })
+                    .map(|token| token.token_probability())
+                    .collect();
+                let avg_token_probability = (!probabilities.is_empty())
+                    .then(|| probabilities.iter().sum::<f32>() / probabilities.len() as f32);
+                Some(TranscriptSegment {
+                    text,
+                    start_ms: segment.start_timestamp() * 10,
+                    end_ms: segment.end_timestamp() * 10,
+                    avg_token_probability,
+                })
+            })
+            .collect();
+
+        let language = language.map(str::to_string).or_else(|| {
+            whisper_rs::get_lang_str(state.full_lang_id_from_state()).map(str::to_string)
+        });
+        Ok(TranscriptionResult {
+            text: segments
+                .iter()
+                .map(|segment| segment.text.as_str())
+                .collect(),
+            segments,
+            language,
+            language_probability,
+            low_language_confidence: language_probability
+                .is_some_and(|probability| probability < LOW_LANGUAGE_CONFIDENCE),
+        })
+    }
+}
+
+// Run whisper's language detection on the first 30s of audio
+fn detect_language(
+    state: &mut whisper_rs::WhisperState,
+    samples: &[f32],
+    n_threads: usize,
+) -> Result<(&'static str, f32), Box<dyn std::error::Error>> {
+    state.pcm_to_mel(samples, n_threads)?;
+    let (lang_id, probabilities) = state.lang_detect(0, n_threads)?;
+    let code = whisper_rs::get_lang_str(lang_id).ok_or("Unknown language id")?;
+    let probability = usize::try_from(lang_id)
+        .ok()
+        .and_then(|index| probabilities.get(index).copied())
+        .unwrap_or(0.0);
+    Ok((code, probability))
+}
+
+#[derive(Debug)]
+struct EngineNotLoaded;
+
+impl std::fmt::Display for EngineNotLoaded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Model not loaded")
+    }
+}
+
+impl std::error::Error for EngineNotLoaded {}
+
+impl WhisperManager {
+    fn transcription_options(
+        &self,
+        app: &AppHandle,
+        initial_prompt: Option<String>,
+    ) -> TranscriptionOptions {
+        let config = load_app_config(app).unwrap_or_default();
+        let languages = config.languages;
+        TranscriptionOptions {
+            decoding: config.decoding.effective(),
+            source_language: languages.language,
+            translate_to_english: languages.translate_to_english,
+            language: self
+                .inner
+                .lock()
+                .current_model
+                .as_deref()
+                .and_then(|name| find_model_info(app, name))
+                .map(|info| info.language)
+                .unwrap_or_default(),
+            initial_prompt,
+        }
+    }
+
+    // Transcribe with the engine of the active model, failing with `EngineNotLoaded` when none
+    // is loaded yet
+    fn transcribe(
+        &self,
+        app: &AppHandle,
+        audio_data: &[f32],
+        initial_prompt: Option<String>,
+    ) -> Result<TranscriptionResult, Box<dyn std::error::Error>> {
+        let options = self.transcription_options(app, initial_prompt);
+        self.transcribe_with(audio_data, &options)
+    }
+
+    fn transcribe_with(
+        &self,
+        audio_data: &[f32],
+        options: &TranscriptionOptions,
+    ) -> Result<TranscriptionResult, Box<dyn std::error::Error>> {
+        let mut runtime = self.inner.lock();
+        let engine = runtime.engine.as_mut().ok_or(EngineNotLoaded)?;
+        transcribe_audio(engine.as_mut(), audio_data, options)
+    }
+
+    // Load the transcription engine for a downloaded model
+    fn load_engine_for(
+        &self,
+        app: &AppHandle,
+        model_name: &str,
+    ) -> Result<Box<dyn TranscriptionEngine>, Box<dyn std::error::Error>> {
+        self.load_engine_at(&get_model_path_for(app, model_name)?)
+    }
+
+    fn load_engine_at(
+        &self,
+        model_path: &Path,
+    ) -> Result<Box<dyn TranscriptionEngine>, Box<dyn std::error::Error>> {
+        if !model_path.exists() {
+            return Err("Model not found. Please download the model first.".into());
+        }
+
+        println!("Loading Whisper model from: {:?}", model_path);
+
+        let engine = (self.load_engine)(model_path)?;
+
+        println!("Whisper model loaded successfully (Metal GPU enabled via feature flag)");
+        Ok(engine)
+    }
+
+    fn transcribe_segments(
+        &self,
+        app: &AppHandle,
+        audio_data: &[f32],
+        initial_prompt: Option<String>,
+    ) -> Result<Vec<TranscriptSegment>, Box<dyn std::error::Error>> {
+        let options = self.transcription_options(app, initial_prompt);
+        let mut runtime = self.inner.lock();
+        let engine = runtime.engine.as_mut().ok_or(EngineNotLoaded)?;
+        Ok(engine.transcribe(audio_data, &options)?.segments)
+    }
+}
+
+// Streaming mode: while the hotkey is held, the audio recorded so far is re-transcribed every
+// `STREAM_STEP`. Once the uncommitted window grows past `STREAM_WINDOW`, all but its last
+// segment are committed and the window slides forward, keeping `STREAM_OVERLAP` of audio
+// before the cut so words are not clipped.
+const STREAM_STEP: Duration = Duration::from_millis(1500);
+// Shortest window worth transcribing (1s)
+const STREAM_MIN_SAMPLES: usize = 16000;
+// Window length at which segments start being committed (10s)
+const STREAM_WINDOW_SAMPLES: usize = 16000 * 10;
+// Commit everything once the window gets this long, even without a segment boundary (25s)
+const STREAM_MAX_WINDOW_SAMPLES: usize = 16000 * 25;
+// Audio before the commit point that is decoded again (200ms)
+const STREAM_OVERLAP_SAMPLES: usize = 16000 / 5;
+// Most words the overlap can repeat at the start of the next window
+const STREAM_OVERLAP_MAX_WORDS: usize = 4;
+// Committed text carried over as the prompt of the next pass
+const STREAM_PROMPT_CHARS: usize = 200;
+
+// Drops words at the start of `text` that repeat the end of `committed`, as happens when the
+// overlap before a commit point is decoded a second time
+fn strip_repeated_words(committed: &str, text: &str) -> String {
+    fn normalize(word: &str) -> String {
+        word.trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase()
+    }
+    let committed: Vec<String> = committed.split_whitespace().map(normalize).collect();
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    let max = STREAM_OVERLAP_MAX_WORDS
+        .min(committed.len())
+        .min(words.len());
+    let repeated = (1..=max).rev().find(|&count| {
+        committed[committed.len() - count..]
+            .iter()
+            .zip(&words[..count])
+            .all(|(committed, word)| !committed.is_empty() && *committed == normalize(word))
+    });
+    match repeated {
+        Some(count) if count == words.len() => String::new(),
+        Some(count) => format!(" {}", words[count..].join(" ")),
+        None => text.to_string(),
+    }
+}
+
+#[derive(Default)]
+struct StreamingProgress {
+    // Samples of the recording covered by `committed_text`
+    committed_samples: usize,
+    committed_text: String,
+    // Configured prompt and vocabulary, resolved once when the session starts
+    base_prompt: Option<String>,
+}
+
+impl StreamingProgress {
+    fn prompt(&self) -> Option<String> {
+        let text = self.committed_text.trim();
+        if text.is_empty() {
+            return self.base_prompt.clone();
+        }
+        let start = text
+            .char_indices()
+            .rev()
+            .nth(STREAM_PROMPT_CHARS - 1)
+            .map(|(index, _)| index)
+            .unwrap_or(0);
+        Some(match &self.base_prompt {
+            Some(base) => format!("{} {}", base, &text[start..]),
+            None => text[start..].to_string(),
+        })
+    }
+
+    fn window_start(&self) -> usize {
+        self.committed_samples
+            .saturating_sub(STREAM_OVERLAP_SAMPLES)
+    }
+
+    // Fold a pass over the `window_len` samples from `window_start` into the committed text
+    // and return the tentative text after it
+    fn apply_pass(
+        &mut self,
+        window_start: usize,
+        window_len: usize,
+        mut segments: Vec<TranscriptSegment>,
+    ) -> String {
+        if let Some(first) = segments.first_mut() {
+            first.text = strip_repeated_words(&self.committed_text, &first.text);
+        }
+
+        let stable_count = if window_len >= STREAM_MAX_WINDOW_SAMPLES {
+            segments.len()
+        } else if window_len >= STREAM_WINDOW_SAMPLES {
+            segments.len().saturating_sub(1)
+        } else {
+            0
+        };
+        if stable_count > 0 {
+            let boundary = if stable_count == segments.len() {
+                window_len
+            } else {
+                let end_ms = segments[stable_count - 1].end_ms.max(0) as usize;
+                (end_ms * 16).min(window_len)
+            };
+            for segment in &segments[..stable_count] {
+                self.committed_text.push_str(&segment.text);
+            }
+            // The next window starts `STREAM_OVERLAP_SAMPLES` before this point
+            self.committed_samples = window_start + boundary;
+        }
+
+        segments[stable_count..]
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect()
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct PartialTranscriptionPayload {
+    committed: String,
+    tentative: String,
+}
+
+// Rolling transcription of one hotkey press; handed to the worker on release
+struct StreamingSession {
+    stop: Arc<AtomicBool>,
+    handle: std::thread::JoinHandle<StreamingProgress>,
+}
+
+impl StreamingSession {
+    fn start(app: AppHandle, whisper: WhisperManager, audio: Arc<Mutex<Vec<f32>>>) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+        let handle = std::thread::spawn(move || {
+            let mut progress = StreamingProgress {
+                base_prompt: configured_initial_prompt(&app),
+                ..Default::default()
+            };
+            let vad = load_app_config(&app)
+                .map(|config| config.vad)
+                .unwrap_or_default();
+            loop {
+                let step_started = Instant::now();
+                while step_started.elapsed() < STREAM_STEP {
+                    if stop_flag.load(Ordering::SeqCst) {
+                        return progress;
+                    }
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+
+                let window_start = progress.window_start();
+                let window: Vec<f32> = {
+                    let buffer = audio.lock();
+                    // Checked under the lock: once stopped, the recorder may reuse the buffer
+                    // for the next recording
+                    if stop_flag.load(Ordering::SeqCst) {
+                        return progress;
+                    }
+                    buffer.get(window_start..).unwrap_or_default().to_vec()
+                };
+                if window.len() < STREAM_MIN_SAMPLES {
+                    continue;
+                }
+                if vad.enabled && detect_speech(&window, &vad).is_none() {
+                    // Whisper tends to invent text for silence, so only windows with speech are
+                    // transcribed. Long silent stretches are skipped over entirely.
+                    if window.len() >= STREAM_WINDOW_SAMPLES {
+                        progress.committed_samples = window_start + window.len();
+                    }
+                    continue;
+                }
+
+                let segments = match whisper.transcribe_segments(&app, &window, progress.prompt()) {
+                    Ok(segments) => segments,
+                    Err(e) => {
+                        eprintln!("Streaming transcription pass failed: {}", e);
+                        continue;
+                    }
+                };
+                if stop_flag.load(Ordering::SeqCst) {
+                    // The recording ended during this pass; the worker finishes the tail
+                    return progress;
+                }
+                let tentative = progress.apply_pass(window_start, window.len(), segments);
+                let _ = app.emit(
+                    "transcription-partial",
+                    PartialTranscriptionPayload {
+                        committed: progress.committed_text.trim().to_string(),
+                        tentative: tentative.trim().to_string(),
+                    },
+                );
+            }
+        });
+        Self { stop, handle }
+    }
+
+    // Called when the hotkey is released, before the recorder's buffer can be reused
+    fn request_stop(&self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+
+    // Stop the rolling passes and return what has been committed so far
+    fn finish(self) -> StreamingProgress {
+        self.stop.store(true, Ordering::SeqCst);
+        self.handle.join().unwrap_or_default()
+    }
+}
+
+// A recorded utterance waiting for the transcription worker
+struct TranscriptionJob {
+    id: u64,
+    samples: Vec<f32>,
+    // Set in streaming mode; only the audio after its committed text still needs transcribing
+    stream: Option<StreamingSession>,
+    // Started while the app being dictated into was frontmost. Streaming jobs use the prompt
+    // their session started with instead.
+    target_app: Option<FrontmostAppLookup>,
+}
+
+// Managed handle used by the shortcut handler to hand recordings to the worker thread, so the
+// hotkey stays responsive while a long clip is transcribed
+#[derive(Clone)]
+struct TranscriptionQueue {
+    sender: mpsc::Sender<TranscriptionJob>,
+    next_id: Arc<AtomicU64>,
+    pending: Arc<AtomicUsize>,
+    // Set while Option+Space is held; pastes wait for it so their synthetic Option release
+    // can't end the next recording early
+    hotkey_held: Arc<AtomicBool>,
+}
+
+impl TranscriptionQueue {
+    fn new(sender: mpsc::Sender<TranscriptionJob>) -> Self {
+        Self {
+            sender,
+            next_id: Arc::new(AtomicU64::new(1)),
+            pending: Arc::new(AtomicUsize::new(0)),
+            hotkey_held: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn set_hotkey_held(&self, held: bool) {
+        self.hotkey_held.store(held, Ordering::SeqCst);
+    }
+
+    // Called from the shortcut handler, so nothing here may block
+    fn enqueue(
+        &self,
+        app: &AppHandle,
+        samples: Vec<f32>,
+        stream: Option<StreamingSession>,
+        target_app: Option<FrontmostAppLookup>,
+    ) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let duration_secs = samples.len() as f32 / 16000.0;
+        let pending = self.pending.fetch_add(1, Ordering::SeqCst) + 1;
+        let job = TranscriptionJob {
+            id,
+            samples,
+            stream,
+            target_app,
+        };
+        if self.sender.send(job).is_err() {
+            self.pending.fetch_sub(1, Ordering::SeqCst);
+            eprintln!("Transcription worker is not running; dropping recording");
+            return;
+        }
+        let _ = app.emit(
+            "transcription-status",
+            TranscriptionEventPayload {
+                job_id: id,
+                status: "queued",
+                duration_secs,
+                pending,
+                ..Default::default()
+            },
+        );
+    }
+}
+
+#[derive(Clone, Serialize, Default)]
+struct TranscriptionEventPayload {
+    #[serde(rename = "jobId")]
+    job_id: u64,
+    status: &'static str,
+    text: Option<String>,
+    error: Option<String>,
+    #[serde(rename = "durationSecs")]
+    duration_secs: f32,
+    // Speech found by voice activity detection; the whole clip when it is disabled
+    #[serde(rename = "speechSecs")]
+    speech_secs: Option<f32>,
+    // Language, confidence and segments of a finished job
+    result: Option<TranscriptionResult>,
+    // Jobs not finished yet, including this one while it is transcribing
+    pending: usize,
+}
+
+// Transcribe queued recordings one at a time and type the text at the cursor
+fn run_transcription_worker(
+    app: AppHandle,
+    whisper: WhisperManager,
+    queue: TranscriptionQueue,
+    jobs: mpsc::Receiver<TranscriptionJob>,
+) {
+    for job in jobs {
+        let duration_secs = job.samples.len() as f32 / 16000.0; // Always 16kHz after resampling
+        let _ = app.emit(
+            "transcription-status",
+            TranscriptionEventPayload {
+                job_id: job.id,
+                status: "transcribing",
+                duration_secs,
+                pending: queue.pending.load(Ordering::SeqCst),
+                ..Default::default()
+            },
+        );
+
+        // Trim silence around the speech; `None` when there is nothing worth transcribing
+        let vad = load_app_config(&app)
+            .map(|config| config.vad)
+            .unwrap_or_default();
+        let span = if vad.enabled {
+            detect_speech(&job.samples, &vad)
+        } else {
+            Some(SpeechSpan {
+                range: 0..job.samples.len(),
+                speech_samples: job.samples.len(),
+            })
+        };
+        let speech_secs = span
+            .as_ref()
+            .map_or(0.0, |span| span.speech_samples as f32 / 16000.0);
+
+        let result = match (job.stream, &span) {
+            (Some(stream), Some(span)) => {
+                let progress = stream.finish();
+                let start = progress.window_start().max(span.range.start);
+                let tail = job.samples.get(start..span.range.end).unwrap_or_default();
+                whisper
+                    .transcribe(&app, tail, progress.prompt())
+                    .map(|mut result| {
+                        // Segments and language describe the tail pass only
+                        let committed = progress.committed_text.trim();
+                        if !committed.is_empty() {
+                            let tail = strip_repeated_words(committed, &result.text);
+                            result.text = if tail.trim().is_empty() {
+                                committed.to_string()
+                            } else {
+                                format!("{} {}", committed, tail.trim())
+                            };
+                        }
+                        result
+                    })
+            }
+            (None, Some(span)) => {
+                let prompt = initial_prompt_for(&app, job.target_app);
+                whisper.transcribe(&app, &job.samples[span.range.clone()], prompt)
+            }
+            // Text committed by streaming passes is dropped too; without speech it can only be
+            // hallucinated
+            (stream, None) => {
+                if let Some(stream) = stream {
+                    stream.finish();
+                }
+                println!("No speech detected, skipping transcription");
+                Ok(TranscriptionResult::default())
+            }
+        };
+
+        let mut payload = TranscriptionEventPayload {
+            job_id: job.id,
+            duration_secs,
+            speech_secs: Some(speech_secs),
+            ..Default::default()
+        };
+        match result {
+            Ok(result) => {
+                if result.text.is_empty() {
+                    println!("No text to insert");
+                } else {
+                    match insert_text_at_cursor(&app, &result.text, &queue.hotkey_held) {
+                        Ok(_) => println!(
+                            "Inserted transcription ({:.2}s): {}",
+                            duration_secs, result.text
+                        ),
+                        Err(e) => eprintln!("Failed to insert text: {}", e),
+                    }
+                }
+                payload.status = "done";
+                payload.text = Some(result.text.clone());
+                payload.result = Some(result);
+            }
+            Err(e) => {
+                if e.is::<EngineNotLoaded>() {
+                    println!("No text to insert ([Model not loaded])");
+                } else {
+                    eprintln!("Transcription failed: {}", e);
+                }
+                payload.status = "failed";
+                payload.error = Some(e.to_string());
+            }
+        }
+
+        payload.pending = queue.pending.fetch_sub(1, Ordering::SeqCst) - 1;
+        let _ = app.emit("transcription-status", payload);
+    }
+}
+
+// How often hands-free mode looks at newly recorded audio
+const HANDS_FREE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Listens while hands-free mode is armed, cutting the live recording into utterances at pauses
+// and queueing each one for transcription
+struct HandsFreeSession {
+    stop: Arc<AtomicBool>,
+    handle: std::thread::JoinHandle<()>,
+}
+
+impl HandsFreeSession {
+    fn start(app: AppHandle, audio: Arc<Mutex<Vec<f32>>>) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+        let handle = std::thread::spawn(move || run_hands_free(app, audio, stop_flag));
+        Self { stop, handle }
+    }
+
+    // Audio after the last queued utterance stays in the recorder's buffer
+    fn finish(self) {
+        self.stop.store(true, Ordering::SeqCst);
+        let _ = self.handle.join();
+    }
+}
+
+fn run_hands_free(app: AppHandle, audio: Arc<Mutex<Vec<f32>>>, stop: Arc<AtomicBool>) {
+    let config = load_app_config(&app).unwrap_or_default();
+    let (vad, settings) = (config.vad, config.hands_free);
+    let mut detector = VoiceActivityDetector::new(&vad);
+
+    let min_frames = ms_to_samples(vad.min_speech_ms).div_ceil(VAD_FRAME_SAMPLES);
+    let padding = ms_to_samples(vad.padding_ms);
+    let silence_timeout = ms_to_samples(settings.silence_timeout_ms);
+    let max_utterance = settings.max_utterance_secs as usize * WHISPER_SAMPLE_RATE as usize;
+
+    // Positions in the live buffer, which is drained as utterances are queued
+    let mut analyzed = 0;
+    let mut run_start: Option<usize> = None;
+    let mut run_frames = 0;
+    let mut utterance: Option<std::ops::Range<usize>> = None;
+
+    while !stop.load(Ordering::SeqCst) {
+        std::thread::sleep(HANDS_FREE_POLL_INTERVAL);
+
+        let mut buffer = audio.lock();
+        while analyzed + VAD_FRAME_SAMPLES <= buffer.len() {
+            let frame_end = analyzed + VAD_FRAME_SAMPLES;
+            if detector.is_speech(&buffer[analyzed..frame_end]) {
+                let start = *run_start.get_or_insert(analyzed);
+                run_frames += 1;
+                match utterance.as_mut() {
+                    Some(utterance) => utterance.end = frame_end,
+                    // Bursts shorter than the minimum speech length, like key clicks, never start one
+                    None if run_frames >= min_frames => utterance = Some(start..frame_end),
+                    None => {}
+                }
+            } else {
+                run_start = None;
+                run_frames = 0;
+            }
+            analyzed = frame_end;
+        }
+
+        let Some(current) = utterance.clone() else {
+            // Nothing to keep apart from the padding before speech that may be starting
+            let keep_from = run_start.unwrap_or(analyzed).saturating_sub(padding);
+            if keep_from > 0 {
+                buffer.drain(..keep_from);
+                analyzed -= keep_from;
+                run_start = run_start.map(|start| start - keep_from);
+            }
+            continue;
+        };
+        if analyzed - current.end < silence_timeout && analyzed - current.start < max_utterance {
+            continue;
+        }
+
+        let start = current.start.saturating_sub(padding);
+        let end = (current.end + padding).min(analyzed);
+        let samples = buffer[start..end].to_vec();
+        buffer.drain(..end);
+        analyzed -= end;
+        run_start = run_start.map(|start| start.saturating_sub(end));
+        utterance = None;
+        drop(buffer);
+
+        println!(
+            "Hands-free utterance of {:.2}s queued",
+            samples.len() as f32 / 16000.0
+        );
+        let queue: tauri::State<TranscriptionQueue> = app.state();
+        queue
+            .inner()
+            .enqueue(&app, samples, None, FrontmostAppLookup::start(&app));
+    }
+}
+
+// Transcribe audio with the given engine
+fn transcribe_audio(
+    engine: &mut dyn TranscriptionEngine,
+    audio_data: &[f32],
+    options: &TranscriptionOptions,
+) -> Result<TranscriptionResult, Box<dyn std::error::Error>> {
+    if audio_data.is_empty() {
+        return Ok(TranscriptionResult::default());
+    }
+
+    // Skip transcription for very short audio (< 0.3s at 16kHz)
+    if audio_data.len() < 4800 {
+        println!(
+            "Audio too short ({} samples), skipping transcription",
+            audio_data.len()
+        );
+        return Ok(TranscriptionResult::default());
+    }
+
+    println!("Starting transcription of {} samples...", audio_data.len());
+
+    let mut result = engine.transcribe(audio_data, options)?;
+
+    result.text = result.text.trim().to_string();
+    println!("Transcription complete: \"{}\"", result.text);
+
+    Ok(result)
+}
+
+// whisper.cpp model files start with the ggml magic, stored little-endian
+const GGML_FILE_MAGIC: u32 = 0x6767_6d6c;
+
+fn validate_ggml_header(path: &Path) -> Result<(), String> {
+    let mut file =
+        std::fs::File::open(path).map_err(|e| format!("Failed to open model file: {}", e))?;
+    let mut magic = [0u8; 4];
+    file.read_exact(&mut magic)
+        .map_err(|_| "File is too small to be a Whisper model.".to_string())?;
+    if u32::from_le_bytes(magic) != GGML_FILE_MAGIC {
+        return Err("Not a ggml Whisper model file (unexpected header).".to_string());
+    }
+    Ok(())
+}
+
+// Whisper expects 16kHz mono audio
+const WHISPER_SAMPLE_RATE: u32 = 16000;
+
+// Microphone capture preferences
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct AudioSettings {
+    // Microphone to record from by name; the system default is used when unset or unplugged
+    input_device: Option<String>,
+    // Zero-based channel to record from; all channels are averaged when unset
+    input_channel: Option<u16>,
+}
+
+// How often the input device list is checked for hot-plugged devices
+const INPUT_DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug, Serialize)]
+struct InputConfigRange {
+    channels: u16,
+    min_sample_rate: u32,
+    max_sample_rate: u32,
+    sample_format: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct InputDeviceInfo {
+    name: String,
+    is_default: bool,
+    configs: Vec<InputConfigRange>,
+}
+
+fn default_input_device_name(host: &cpal::Host) -> Option<String> {
+    host.default_input_device()
+        .and_then(|device| device.name().ok())
+}
+
+// Names of the connected input devices and of the default one, cheap enough to poll
+fn input_device_names(
+    host: &cpal::Host,
+) -> Result<(Vec<String>, Option<String>), Box<dyn std::error::Error>> {
+    let names = host
+        .input_devices()?
+        .filter_map(|device| device.name().ok())
+        .collect();
+    Ok((names, default_input_device_name(host)))
+}
+
+fn list_input_devices() -> Result<Vec<InputDeviceInfo>, Box<dyn std::error::Error>> {
+    let host = cpal::default_host();
+    let default_name = default_input_device_name(&host);
+    let devices = host
+        .input_devices()?
+        .filter_map(|device| {
+            // Devices that vanish mid-enumeration have no name; skip them
+            let name = device.name().ok()?;
+            let configs = device
+                .supported_input_configs()
+                .map(|configs| {
+                    configs
+                        .map(|config| InputConfigRange {
+                            channels: config.channels(),
+                            min_sample_rate: config.min_sample_rate().0,
+                            max_sample_rate: config.max_sample_rate().0,
+                            sample_format: config.sample_format().to_string(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(InputDeviceInfo {
+                is_default: default_name.as_deref() == Some(name.as_str()),
+                name,
+                configs,
+            })
+        })
+        .collect();
+    Ok(devices)
+}
+
+// The preferred device if it is connected, otherwise the system default
+fn find_input_device(
+    host: &cpal::Host,
+    preferred: Option<&str>,
+) -> Result<cpal::Device, Box<dyn std::error::Error>> {
+    if let Some(preferred) = preferred {
+        let found = host
+            .input_devices()?
+            .find(|device| device.name().is_ok_and(|name| name == preferred));
+        match found {
+            Some(device) => return Ok(device),
+            None => println!(
+                "Input device '{}' not found, using the default device",
+                preferred
+            ),
+        }
+    }
+    Ok(host
+        .default_input_device()
+        .ok_or("No input device available")?)
+}
+
+// Emits `input-devices-changed` with the full device list whenever a device is plugged in or
+// removed, or the default changes
+fn run_input_device_monitor(app: AppHandle) {
+    let host = cpal::default_host();
+    let mut last = input_device_names(&host).ok();
+    loop {
+        std::thread::sleep(INPUT_DEVICE_POLL_INTERVAL);
+        let current = match input_device_names(&host) {
+            Ok(current) => current,
+            Err(e) => {
+                eprintln!("Failed to enumerate input devices: {}", e);
+                continue;
+            }
+        };
+        if last.as_ref() == Some(&current) {
+            continue;
+        }
+        println!("Input devices changed: {:?}", current.0);
+        last = Some(current);
+        match list_input_devices() {
+            Ok(devices) => {
+                let _ = app.emit("input-devices-changed", devices);
+            }
+            Err(e) => eprintln!("Failed to list input devices: {}", e),
+        }
+    }
+}
+
+// How interleaved device frames become the mono signal Whisper hears
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ChannelMix {
+    Downmix(usize),
+    Select { channel: usize, channels: usize },
+}
+
+impl ChannelMix {
+    fn new(channels: u16, input_channel: Option<u16>) -> Self {
+        let channels = usize::from(channels.max(1));
+        match input_channel.map(usize::from) {
+            Some(channel) if channel < channels => ChannelMix::Select { channel, channels },
+            Some(channel) => {
+                println!(
+                    "Input channel {} not available on a {}-channel device, downmixing instead",
+                    channel + 1,
+                    channels
+                );
+                ChannelMix::Downmix(channels)
+            }
+            None => ChannelMix::Downmix(channels),
+        }
+    }
+
+    fn channels(self) -> usize {
+        match self {
+            ChannelMix::Downmix(channels) | ChannelMix::Select { channels, .. } => channels,
+        }
+    }
+
+    // One mono sample per complete frame; cpal callbacks always deliver whole frames
+    fn frames<'a, T>(self, data: &'a [T]) -> impl Iterator<Item = f32> + 'a
+    where
+        T: cpal::Sample + 'a,
+        f32: FromSample<T>,
+    {
+        data.chunks_exact(self.channels())
+            .map(move |frame| match self {
+                ChannelMix::Downmix(channels) => {
+                    frame.iter().map(|&s| f32::from_sample(s)).sum::<f32>() / channels as f32
+                }
+                ChannelMix::Select { channel, .. } => f32::from_sample(frame[channel]),
+            })
+    }
+}
+// 16kHz samples per resampler FFT (16ms); it also sets the filter's sharpness, so it is the
+// same for every device rate
+const RESAMPLER_FFT_OUTPUT_FRAMES: usize = 256;
+const RESAMPLER_SUB_CHUNKS: usize = 2;
+
+// Device frames per resampler pass, adjusted so the FFT resampler's delay is a whole number of
+// samples on both sides. Dropping a fractional delay would shift the phase.
+fn resampler_chunk_frames(input_rate: u32) -> usize {
+    let (mut a, mut b) = (input_rate as usize, WHISPER_SAMPLE_RATE as usize);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    let (frames_in, frames_out) = (input_rate as usize / a, WHISPER_SAMPLE_RATE as usize / a);
+
+    let mut fft_chunks = RESAMPLER_FFT_OUTPUT_FRAMES.div_ceil(frames_out);
+    // Both FFT sizes need to be even; the two factors share no divisor, so an even count works
+    if (fft_chunks * frames_in) % 2 == 1 || (fft_chunks * frames_out) % 2 == 1 {
+        fft_chunks += 1;
+    }
+    fft_chunks * frames_in * RESAMPLER_SUB_CHUNKS
+}
+
+// Streaming conversion from the device rate to 16kHz. Uses rubato's FFT resampler, which
+// low-pass filters before decimating, so any input rate works without aliasing.
+struct StreamResampler {
+    // `None` when the device already records at 16kHz
+    resampler: Option<FftFixedIn<f32>>,
+    input_rate: u32,
+    pending: Vec<f32>,
+    // Leading output frames that are only filter delay
+    skip_frames: usize,
+    frames_in: u64,
+    frames_out: u64,
+}
+
+impl StreamResampler {
+    fn new(input_rate: u32) -> Result<Self, Box<dyn std::error::Error>> {
+        let resampler = if input_rate == WHISPER_SAMPLE_RATE {
+            None
+        } else {
+            Some(FftFixedIn::<f32>::new(
+                input_rate as usize,
+                WHISPER_SAMPLE_RATE as usize,
+                resampler_chunk_frames(input_rate),
+                RESAMPLER_SUB_CHUNKS,
+                1,
+            )?)
+        };
+        Ok(Self {
+            skip_frames: resampler.as_ref().map_or(0, |r| r.output_delay()),
+            resampler,
+            input_rate,
+            pending: Vec::new(),
+            frames_in: 0,
+            frames_out: 0,
+        })
+    }
+
+    // Resample whole chunks of `samples` into `out`; the remainder waits for the next call
+    fn push(&mut self, samples: impl IntoIterator<Item = f32>, out: &mut Vec<f32>) {
+        let Some(resampler) = self.resampler.as_mut() else {
+            out.extend(samples);
+            return;
+        };
+
+        let before = self.pending.len();
+        self.pending.extend(samples);
+        self.frames_in += (self.pending.len() - before) as u64;
+
+        let mut consumed = 0;
+        while self.pending.len() - consumed >= resampler.input_frames_next() {
+            let frames = resampler.input_frames_next();
+            match resampler.process(&[&self.pending[consumed..consumed + frames]], None) {
+                Ok(output) => {
+                    let skip = self.skip_frames.min(output[0].len());
+                    self.skip_frames -= skip;
+                    self.frames_out += (output[0].len() - skip) as u64;
+                    out.extend_from_slice(&output[0][skip..]);
+                }
+                Err(e) => eprintln!("Resampling failed: {}", e),
+            }
+            consumed += frames;
+        }
+        self.pending.drain(..consumed);
+    }
+
+    // Resample what is left at the end of a recording and drain the filter delay
+    fn flush(&mut self, out: &mut Vec<f32>) {
+        let Some(resampler) = self.resampler.as_mut() else {
+            return;
+        };
+
+        let expected = self.frames_in * u64::from(WHISPER_SAMPLE_RATE) / u64::from(self.input_rate);
+        // rubato treats an empty channel as inactive, so only pass real leftovers
+        let mut remaining = Some(std::mem::take(&mut self.pending)).filter(|p| !p.is_empty());
+        // Each partial pass yields one chunk, so a few passes always cover the delay
+        for _ in 0..4 {
+            if self.frames_out >= expected {
+                break;
+            }
+            let input = remaining.take();
+            let result = match &input {
+                Some(samples) => resampler.process_partial(Some(&[samples.as_slice()]), None),
+                None => resampler.process_partial(None::<&[&[f32]]>, None),
+            };
+            match result {
+                Ok(output) => {
+                    let skip = self.skip_frames.min(output[0].len());
+                    self.skip_frames -= skip;
+                    let wanted = (expected - self.frames_out) as usize;
+                    let produced = &output[0][skip..];
+                    let take = produced.len().min(wanted);
+                    self.frames_out += take as u64;
+                    out.extend_from_slice(&produced[..take]);
+                }
+                Err(e) => {
+                    eprintln!("Resampling failed: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+// Voice activity detection runs on the 16kHz signal in 20ms frames
+const VAD_FRAME_SAMPLES: usize = 320;
+// Bounds for the speech threshold, which otherwise follows the background noise
+const VAD_MIN_THRESHOLD_DB: f32 = -55.0;
+const VAD_MAX_THRESHOLD_DB: f32 = -30.0;
+// Frames crossing zero more often than this (per sample) are hiss or broadband noise
+const VAD_MAX_SPEECH_ZCR: f32 = 0.35;
+// Pauses up to this long stay inside one stretch of speech
+const VAD_HANGOVER_FRAMES: usize = 15; // 300ms
+
+// Silence trimming before transcription; clips without speech are not transcribed at all,
+// which avoids Whisper inventing text such as "Thank you." for silence
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct VadSettings {
+    enabled: bool,
+    // How far above the background noise a frame must be to count as speech
+    threshold_db: f32,
+    // Shorter bursts, such as key clicks, are ignored
+    min_speech_ms: u32,
+    // Audio kept on either side of the detected speech
+    padding_ms: u32,
+}
+
+impl Default for VadSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold_db: 12.0,
+            min_speech_ms: 200,
+            padding_ms: 300,
+        }
+    }
+}
+
+impl VadSettings {
+    fn validate(&self) -> Result<(), String> {
+        if !(3.0..=40.0).contains(&self.threshold_db) {
+            return Err("Speech threshold must be between 3 and 40 dB.".to_string());
+        }
+        if !(20..=2000).contains(&self.min_speech_ms) {
+            return Err("Minimum speech length must be between 20 and 2000 ms.".to_string());
+        }
+        if self.padding_ms > 2000 {
+            return Err("Speech padding must be at most 2000 ms.".to_string());
+        }
+        Ok(())
+    }
+}
+
+// Voice-activated recording toggled with Option+Shift+Space
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct HandsFreeSettings {
+    // Pause that ends an utterance and sends it for transcription
+    silence_timeout_ms: u32,
+    // Longer utterances are cut and transcribed in pieces
+    max_utterance_secs: u32,
+}
+
+impl Default for HandsFreeSettings {
+    fn default() -> Self {
+        Self {
+            silence_timeout_ms: 800,
+            max_utterance_secs: 30,
+        }
+    }
+}
+
+impl HandsFreeSettings {
+    fn validate(&self) -> Result<(), String> {
+        if !(200..=5000).contains(&self.silence_timeout_ms) {
+            return Err("Silence timeout must be between 200 and 5000 ms.".to_string());
+        }
+        if !(2..=120).contains(&self.max_utterance_secs) {
+            return Err("Maximum utterance length must be between 2 and 120 seconds.".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn ms_to_samples(ms: u32) -> usize {
+    ms as usize * WHISPER_SAMPLE_RATE as usize / 1000
+}
+
+// RMS level in dBFS and zero-crossing rate of one frame
+fn frame_features(frame: &[f32]) -> (f32, f32) {
+    if frame.is_empty() {
+        return (f32::NEG_INFINITY, 0.0);
+    }
+    let energy = frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32;
+    let crossings = frame
+        .windows(2)
+        .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+        .count();
+    (
+        10.0 * energy.max(1e-12).log10(),
+        crossings as f32 / frame.len() as f32,
+    )
+}
+
+// Frame-by-frame speech classifier with an adaptive noise floor
+struct VoiceActivityDetector {
+    margin_db: f32,
+    noise_floor_db: f32,
+}
+
+impl VoiceActivityDetector {
+    fn new(settings: &VadSettings) -> Self {
+        Self {
+            margin_db: settings.threshold_db,
+            noise_floor_db: VAD_MIN_THRESHOLD_DB - settings.threshold_db,
+        }
+    }
+
+    fn threshold_db(&self) -> f32 {
+        (self.noise_floor_db + self.margin_db).clamp(VAD_MIN_THRESHOLD_DB, VAD_MAX_THRESHOLD_DB)
+    }
+
+    fn is_speech(&mut self, frame: &[f32]) -> bool {
+        let (energy_db, zcr) = frame_features(frame);
+        let speech = energy_db > self.threshold_db() && zcr < VAD_MAX_SPEECH_ZCR;
+
+        // Follow the background down quickly and up slowly, barely moving during speech
+        let rate = if energy_db < self.noise_floor_db {
+            0.2
+        } else if speech {
+            0.002
+        } else {
+            0.02
+        };
+        if energy_db.is_finite() {
+            self.noise_floor_db += (energy_db - self.noise_floor_db) * rate;
+        }
+        speech
+    }
+}
+
+// Where the speech is in a clip, padded, and how much of it there is
+#[derive(Clone, Debug)]
+struct SpeechSpan {
+    range: std::ops::Range<usize>,
+    speech_samples: usize,
+}
+
+// Finds the speech in a whole 16kHz clip; `None` if there is none
+fn detect_speech(samples: &[f32], settings: &VadSettings) -> Option<SpeechSpan> {
+    // Start from the lowest threshold and let the floor rise with the background. Seeding it
+    // from the clip itself fails when speech fills the whole clip, since the seed is then speech.
+    let mut vad = VoiceActivityDetector::new(settings);
+
+    // Stretches of speech frames, bridging short pauses
+    let mut regions: Vec<std::ops::Range<usize>> = Vec::new();
+    let mut silent_frames = VAD_HANGOVER_FRAMES + 1;
+    for (index, frame) in samples.chunks(VAD_FRAME_SAMPLES).enumerate() {
+        if vad.is_speech(frame) {
+            match regions.last_mut() {
+                Some(region) if silent_frames <= VAD_HANGOVER_FRAMES => region.end = index + 1,
+                _ => regions.push(index..index + 1),
+            }
+            silent_frames = 0;
+        } else {
+            silent_frames += 1;
+        }
+    }
+
+    let min_frames = ms_to_samples(settings.min_speech_ms).div_ceil(VAD_FRAME_SAMPLES);
+    regions.retain(|region| region.len() >= min_frames);
+    let first = regions.first()?;
+    let last = regions.last()?;
+
+    let padding = ms_to_samples(settings.padding_ms);
+    let start = (first.start * VAD_FRAME_SAMPLES).saturating_sub(padding);
+    let end = (last.end * VAD_FRAME_SAMPLES + padding).min(samples.len());
+    let speech_frames: usize = regions.iter().map(|region| region.len()).sum();
+    Some(SpeechSpan {
+        range: start..end,
+        speech_samples: (speech_frames * VAD_FRAME_SAMPLES).min(samples.len()),
+    })
+}
+
+// Audio recording state - stores the stream and buffers audio data
+struct AudioRecorder {
+    stream: Option<cpal::Stream>,
+    buffer: Arc<Mutex<Vec<f32>>>,
+    sample_rate: u32,
+    resampler: Option<Arc<Mutex<StreamResampler>>>,
+}
+
+// Safety: AudioRecorder is only accessed from the main thread via parking_lot::Mutex
+unsafe impl Send for AudioRecorder {}
+unsafe impl Sync for AudioRecorder {}
+
+impl AudioRecorder {
+    fn new() -> Self {
+        Self {
+            stream: None,
+            buffer: Arc::new(Mutex::new(Vec::new())),
+            sample_rate: 0,
+            resampler: None,
+        }
+    }
+
+    fn start(&mut self, settings: &AudioSettings) -> Result<(), Box<dyn std::error::Error>> {
+        if self.stream.is_none() {
+            // Clear previous buffers
+            self.buffer.lock().clear();
+
+            // Get the audio host and the preferred (or default) input device
+            let host = cpal::default_host();
+            let device = find_input_device(&host, settings.input_device.as_deref())?;
+
+            // Try to get 16kHz config (Whisper requirement)
+            let config = match device.supported_input_configs() {
+                Ok(configs) => {
+                    // Try to find a 16kHz mono config
+                    let mut found_16khz = None;
+                    for config in configs {
+                        if config.min_sample_rate().0 <= WHISPER_SAMPLE_RATE
+                            && config.max_sample_rate().0 >= WHISPER_SAMPLE_RATE
+                        {
+                            // Found a config that supports 16kHz
+                            found_16khz = Some(
+                                config.with_sample_rate(cpal::SampleRate(WHISPER_SAMPLE_RATE)),
+                            );
+                            break;
+                        }
+                    }
+
+                    match found_16khz {
+                        Some(cfg) => cfg,
+                        None => {
+                            // Fallback to default config if 16kHz not supported
+                            println!("16kHz not supported, using default config");
+                            device.default_input_config()?
+                        }
+                    }
+                }
+                Err(_) => device.default_input_config()?,
+            };
+
+            self.sample_rate = config.sample_rate().0;
+            println!("Starting audio capture with config: {:?}", config);
+
+            // Create the audio stream based on sample format with buffering
+            let buffer_clone = self.buffer.clone();
+            let resampler = Arc::new(Mutex::new(StreamResampler::new(self.sample_rate)?));
+            self.resampler = Some(resampler.clone());
+            let mix = ChannelMix::new(config.channels(), settings.input_channel);
+
+            let stream = match config.sample_format() {
+                cpal::SampleFormat::F32 => build_input_stream::<f32>(
+                    &device,
+                    &config.into(),
+                    mix,
+                    buffer_clone,
+                    resampler,
+                )?,
+                cpal::SampleFormat::I16 => build_input_stream::<i16>(
+                    &device,
+                    &config.into(),
+                    mix,
+                    buffer_clone,
+                    resampler,
+                )?,
+                cpal::SampleFormat::U16 => build_input_stream::<u16>(
+                    &device,
+                    &config.into(),
+                    mix,
+                    buffer_clone,
+                    resampler,
+                )?,
+                _ => return Err("Unsupported sample format".into()),
+            };
+
+            stream.play()?;
+            self.stream = Some(stream);
+        }
+        Ok(())
+    }
+
+    fn is_recording(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    // The 16kHz buffer filled while recording, for streaming transcription and hands-free mode
+    fn live_buffer(&self) -> Arc<Mutex<Vec<f32>>> {
+        self.buffer.clone()
+    }
+
+    fn stop(&mut self) -> Vec<f32> {
+        if let Some(stream) = self.stream.take() {
+            drop(stream);
+            println!("Audio capture stopped - microphone released");
+        }
+
+        // Resample the last partial chunk, then take the buffered 16kHz audio
+        if let Some(resampler) = self.resampler.take() {
+            resampler.lock().flush(&mut self.buffer.lock());
+        }
+        let audio_data = std::mem::take(&mut *self.buffer.lock());
+
+        println!(
+            "Captured {} samples at 16kHz (recorded at {}Hz)",
+            audio_data.len(),
+            self.sample_rate
+        );
+
+        audio_data
+    }
+}
+
+fn build_input_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mix: ChannelMix,
+    buffer: Arc<Mutex<Vec<f32>>>,
+    resampler: Arc<Mutex<StreamResampler>>,
+) -> Result<cpal::Stream, Box<dyn std::error::Error>>
+where
+    T: cpal::Sample + cpal::SizedSample,
+    f32: FromSample<T>,
+{
+    let stream = device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            // Mix interleaved frames down to mono and resample to 16kHz in real-time
+            let mut resampler = resampler.lock();
+            let mut buf = buffer.lock();
+            resampler.push(mix.frames(data), &mut buf);
+        },
+        |err| eprintln!("Audio stream error: {}", err),
+        None,
+    )?;
+
+    Ok(stream)
+}
+
+// Insert text at cursor position using clipboard save/restore + paste
+fn insert_text_at_cursor(
+    app: &AppHandle,
+    text: &str,
+    hotkey_held: &AtomicBool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // A new recording may have started while this one was transcribed; paste once it ends
+    if hotkey_held.load(Ordering::SeqCst) {
+        println!("Waiting for Option+Space to be released before pasting");
+        while hotkey_held.load(Ordering::SeqCst) {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    }
+
+    // Save current clipboard content
+    let original_clipboard = app.clipboard().read_text().ok();
+
+    // Write our text to clipboard
+    app.clipboard().write_text(text)?;
+
+    // Wait for clipboard to update and for user to release modifier keys
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    // Release Option key if it's still held (to avoid Cmd+Option+V)
+    let mut enigo = Enigo::new(&Settings::default())?;
+    enigo.key(Key::Alt, enigo::Direction::Release)?;
+
+    // Small delay after releasing Option
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    // Simulate Cmd+V to paste
+    enigo.key(Key::Meta, enigo::Direction::Press)?;
+    enigo.key(Key::Unicode('v'), enigo::Direction::Click)?;
+    enigo.key(Key::Meta, enigo::Direction::Release)?;
+
+    // Wait a bit for paste to complete
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    // Restore original clipboard content
+    if let Some(original) = original_clipboard {
+        let _ = app.clipboard().write_text(original);
+    }
+
+    println!("Inserted text via clipboard (restored original)");
+
+    Ok(())
+}
+
+// Tauri command to get list of downloaded models
+#[tauri::command]
+fn get_downloaded_models(app: tauri::AppHandle) -> Vec<String> {
+    get_available_models(&app)
+        .into_iter()
+        .filter(|model| model_exists_for(&app, &model.name))
+        .map(|model| model.name)
+        .collect()
+}
+
+#[tauri::command]
+fn get_model_catalog(app: tauri::AppHandle) -> Vec<ModelInfo> {
+    get_available_models(&app)
+}
+
+#[tauri::command]
+fn add_custom_model(
+    app: tauri::AppHandle,
+    catalog: tauri::State<'_, ModelCatalog>,
+    model: ModelInfo,
+) -> Result<(), String> {
+    let mut model = model;
+    model.custom = true;
+
+    {
+        let mut custom = catalog.inner().custom.lock();
+        validate_custom_model(&mut model, &custom, true)?;
+        let mut updated = custom.clone();
+        updated.push(model.clone());
+        save_custom_models(&app, &updated)?;
+        *custom = updated;
+    }
+
+    println!("Added custom model '{}'", model.name);
+    let _ = app.emit("model-catalog-changed", get_available_models(&app));
+    Ok(())
+}
+
+// Drop a custom entry from the catalog, optionally deleting its downloaded file
+#[tauri::command]
+fn remove_custom_model(
+    app: tauri::AppHandle,
+    catalog: tauri::State<'_, ModelCatalog>,
+    downloads: tauri::State<'_, DownloadManager>,
+    whisper: tauri::State<'_, WhisperManager>,
+    model_name: String,
+    delete_file: bool,
+) -> Result<(), String> {
+    let is_active = {
+        let runtime = whisper.inner().inner.lock();
+        runtime.current_model.as_deref() == Some(model_name.as_str())
+    };
+    if is_active {
+        return Err(
+            "Model is currently active. Switch to another model before removing.".to_string(),
+        );
+    }
+
+    {
+        let map = downloads.inner().inner.lock();
+        if let Some(entry) = map.get(&model_name) {
+            if entry.status.is_in_progress() {
+                return Err("Download in progress. Cancel it before removing.".to_string());
+            }
+        }
+    }
+
+    if !catalog
+        .inner()
+        .custom
+        .lock()
+        .iter()
+        .any(|model| model.name == model_name)
+    {
+        return Err("Not a custom model.".to_string());
+    }
+
+    // Resolve paths while the entry is still in the catalog
+    let model_path = get_model_path_for(&app, &model_name)?;
+    let temp_path = get_temp_path_for(&model_path);
+
+    {
+        let mut custom = catalog.inner().custom.lock();
+        let updated: Vec<ModelInfo> = custom
+            .iter()
+            .filter(|model| model.name != model_name)
+            .cloned()
+            .collect();
+        save_custom_models(&app, &updated)?;
+        *custom = updated;
+    }
+
+    if delete_file {
+        let validator_path = get_validator_path_for(&temp_path);
+        for path in [&model_path, &temp_path, &validator_path] {
+            if path.exists() {
+                std::fs::remove_file(path)
+                    .map_err(|e| format!("Failed to remove model file: {}", e))?;
+            }
+        }
+    }
+
+    downloads.inner().inner.lock().remove(&model_name);
+
+    println!("Removed custom model '{}'", model_name);
+    let _ = app.emit("model-catalog-changed", get_available_models(&app));
+    Ok(())
+}
+
+// Register a ggml model file from disk (e.g. on machines without internet access) by copying
+// or hard-linking it into the models folder and adding it to the custom catalog
+#[tauri::command]
+async fn import_model_file(
+    app: tauri::AppHandle,
+    catalog: tauri::State<'_, ModelCatalog>,
+    downloads: tauri::State<'_, DownloadManager>,
+    path: String,
+    model_name: Option<String>,
+    link: bool,
+) -> Result<ModelInfo, String> {
+    let catalog = catalog.inner().clone();
+    let downloads = downloads.inner().clone();
+    // The trial load, the copy and the hash can each take a while on multi-GB models
+    tauri::async_runtime::spawn_blocking(move || {
+        import_model_file_blocking(&app, &catalog, &downloads, path, model_name, link)
+    })
+    .await
+    .map_err(|e| format!("Model import failed: {}", e))
+    .and_then(|result| result)
+}
+
+fn import_model_file_blocking(
+    app: &AppHandle,
+    catalog: &ModelCatalog,
+    downloads: &DownloadManager,
+    path: String,
+    model_name: Option<String>,
+    link: bool,
+) -> Result<ModelInfo, String> {
+    let source = PathBuf::from(path);
+    if !source.is_file() {
+        return Err(format!("Model file not found: {}", source.display()));
+    }
+    let filename = source
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(str::to_string)
+        .ok_or_else(|| "Invalid model file name".to_string())?;
+
+    validate_ggml_header(&source)?;
+
+    println!("Trial-loading imported model: {:?}", source);
+    let language = {
+        let engine = WhisperEngine::load(&source)
+            .map_err(|e| format!("Whisper could not load this model: {}", e))?;
+        if engine.capabilities().multilingual {
+            ModelLanguage::Multilingual
+        } else {
+            ModelLanguage::English
+        }
+    };
+
+    let size_bytes = std::fs::metadata(&source)
+        .map_err(|e| format!("Failed to read model file: {}", e))?
+        .len();
+
+    // A copy of an official model keeps its file name; install it as that built-in
+    if let Some(builtin) = get_builtin_models()
+        .into_iter()
+        .find(|model| model.filename == filename)
+    {
+        return import_builtin_model_file(app, downloads, builtin, &source, size_bytes, link);
+    }
+
+    let name = model_name.unwrap_or_else(|| {
+        let stem = filename.trim_end_matches(".bin");
+        stem.strip_prefix("ggml-").unwrap_or(stem).to_string()
+    });
+
+    let mut model = ModelInfo {
+        name,
+        filename,
+        url: None,
+        local_path: Some(source.clone()),
+        size_mb: size_bytes.div_ceil(1024 * 1024).max(1) as u32,
+        sha256: None,
+        language,
+        custom: true,
+    };
+    validate_custom_model(&mut model, &catalog.custom.lock(), true)?;
+
+    let destination = get_model_base_path(app)?.join(&model.filename);
+    let (linked, sha256) = place_imported_model_file(&source, &destination, link)?;
+    model.sha256 = Some(sha256.clone());
+
+    {
+        let mut custom = catalog.custom.lock();
+        let mut updated = custom.clone();
+        updated.push(model.clone());
+        if let Err(err) = save_custom_models(app, &updated) {
+            let _ = std::fs::remove_file(&destination);
+            return Err(err);
+        }
+        *custom = updated;
+    }
+
+    record_model_checksum(app, &model.name, &sha256);
+
+    println!(
+        "Imported model '{}' ({}) from {:?}",
+        model.name,
+        if linked { "hard link" } else { "copy" },
+        source
+    );
+
+    let _ = app.emit("model-catalog-changed", get_available_models(app));
+    emit_download_event(
+        app,
+        DownloadEventPayload {
+            model_name: model.name.clone(),
+            downloaded_bytes: size_bytes,
+            total_bytes: Some(size_bytes),
+            percent: Some(100.0),
+            status: "completed",
+            ..Default::default()
+        },
+    );
+
+    Ok(model)
+}
+
+// Hard-link or copy an imported file to `destination` and hash it. Returns whether it was linked.
+fn place_imported_model_file(
+    source: &Path,
+    destination: &Path,
+    link: bool,
+) -> Result<(bool, String), String> {
+    if destination.exists() {
+        return Err(format!(
+            "A file named '{}' already exists in the models folder.",
+            destination
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+        ));
+    }
+
+    let linked = link && std::fs::hard_link(source, destination).is_ok();
+    if !linked {
+        std::fs::copy(source, destination)
+            .map_err(|e| format!("Failed to copy model file: {}", e))?;
+    }
+
+    match compute_sha256(destination) {
+        Ok(sha256) => Ok((linked, sha256)),
+        Err(e) => {
+            let _ = std::fs::remove_file(destination);
+            Err(format!("Failed to hash model file: {}", e))
+        }
+    }
+}
+
+// Install an imported copy of a built-in model as if it had been downloaded. It is checked
+// against the pinned checksum when there is one, and reported as unverified otherwise.
+fn import_builtin_model_file(
+    app: &AppHandle,
+    downloads: &DownloadManager,
+    model: ModelInfo,
+    source: &Path,
+    size_bytes: u64,
+    link: bool,
+) -> Result<ModelInfo, String> {
+    // The download would replace the imported file when it finishes
+    if let Some(entry) = downloads.inner.lock().get(&model.name) {
+        if entry.status.is_in_progress() {
+            return Err(format!(
+                "A download of '{}' is queued or in progress. Cancel it before importing.",
+                model.name
+            ));
+        }
+    }
+
+    let destination = get_model_path_for(app, &model.name)?;
+    let (linked, sha256) = place_imported_model_file(source, &destination, link)?;
+
+    let verified = match &model.sha256 {
+        Some(expected) if *expected != sha256 => {
+            let _ = std::fs::remove_file(&destination);
+            return Err(format!(
+                "This file does not match the official '{}' model.",
+                model.name
+            ));
+        }
+        Some(_) => {
+            record_model_checksum(app, &model.name, &sha256);
+            true
+        }
+        None => false,
+    };
+    set_model_removed(app, &model.name, false);
+
+    println!(
+        "Imported built-in model '{}' ({}{}) from {:?}",
+        model.name,
+        if linked { "hard link" } else { "copy" },
+        if verified { "" } else { ", unverified" },
+        source
+    );
+
+    emit_download_event(
+        app,
+        DownloadEventPayload {
+            model_name: model.name.clone(),
+            downloaded_bytes: size_bytes,
+            total_bytes: Some(size_bytes),
+            percent: Some(100.0),
+            status: "completed",
+            verified: Some(verified),
+            ..Default::default()
+        },
+    );
+
+    Ok(model)
+}
+
+#[tauri::command]
+fn get_model_statuses(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+    whisper: tauri::State<'_, WhisperManager>,
+) -> Vec<ModelStatus> {
+    gather_model_statuses(&app, downloads.inner(), whisper.inner())
+}
+
+#[tauri::command]
+fn start_model_download(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+    whisper: tauri::State<'_, WhisperManager>,
+    model_name: String,
+) -> Result<(), String> {
+    spawn_model_download(
+        &app,
+        downloads.inner().clone(),
+        whisper.inner().clone(),
+        model_name.clone(),
+        false,
+    )?;
+    set_model_removed(&app, &model_name, false);
+    Ok(())
+}
+
+#[tauri::command]
+fn refresh_model_download(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+    whisper: tauri::State<'_, WhisperManager>,
+    model_name: String,
+) -> Result<(), String> {
+    // Prevent refresh while download already in progress
+    {
+        let map = downloads.inner().inner.lock();
+        if let Some(entry) = map.get(&model_name) {
+            if entry.status.is_in_progress() {
+                return Err("Download already in progress".to_string());
+            }
+        }
+    }
+
+    spawn_model_download(
+        &app,
+        downloads.inner().clone(),
+        whisper.inner().clone(),
+        model_name.clone(),
+        true,
+    )?;
+    set_model_removed(&app, &model_name, false);
+    Ok(())
+}
+
+#[tauri::command]
+fn remove_model(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+    whisper: tauri::State<'_, WhisperManager>,
+    model_name: String,
+) -> Result<(), String> {
+    println!("remove_model called for {}", model_name);
+    let is_active = {
+        let runtime = whisper.inner().inner.lock();
+        runtime
+            .current_model
+            .as_ref()
+            .map(|current| current == &model_name)
+            .unwrap_or(false)
+    };
+
+    if is_active {
+        println!("remove_model abort: {} is active", model_name);
+        return Err(
+            "Model is currently active. Switch to another model before removing.".to_string(),
+        );
+    }
+
+    {
+        let map = downloads.inner().inner.lock();
+        if let Some(entry) = map.get(&model_name) {
+            if entry.status.is_in_progress() {
+                return Err("Download in progress. Cancel it before removing.".to_string());
+            }
+        }
+    }
+
+    let model_path = get_model_path_for(&app, &model_name)?;
+    let temp_path = get_temp_path_for(&model_path);
+    if model_path.exists() {
+        std::fs::remove_file(&model_path).map_err(|e| format!("Failed to remove model: {}", e))?;
+        println!("Removed file: {:?}", model_path);
+    } else if temp_path.exists() {
+        println!(
+            "remove_model: discarding partial download for {}",
+            model_name
+        );
+    } else {
+        println!("remove_model abort: file not found for {}", model_name);
+        return Err("Model file not found.".to_string());
+    }
+
+    remove_partial_download(&temp_path)
+        .map_err(|e| format!("Failed to remove partial download: {}", e))?;
+
+    {
+        let mut map = downloads.inner().inner.lock();
+        map.remove(&model_name);
+    }
+
+    set_model_removed(&app, &model_name, true);
+
+    emit_download_event(
+        &app,
+        DownloadEventPayload {
+            model_name: model_name.clone(),
+            downloaded_bytes: 0,
+            total_bytes: None,
+            percent: None,
+            status: "removed",
+            error: None,
+            ..Default::default()
+        },
+    );
+
+    Ok(())
+}
+
+// Drop a waiting download from the queue, or ask a running download thread to stop; the
+// thread removes the partial file and emits "cancelled" itself
+#[tauri::command]
+fn cancel_model_download(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+    model_name: String,
+) -> Result<(), String> {
+    let downloads = downloads.inner();
+    let was_queued = {
+        let mut queue = downloads.queue.lock();
+        match queue
+            .pending
+            .iter()
+            .position(|item| item.model_name == model_name)
+        {
+            Some(index) => {
+                queue.pending.remove(index);
+                true
+            }
+            None => false,
+        }
+    };
+
+    if was_queued {
+        // A queued download may still have a partial file from an earlier, interrupted attempt
+        let temp_path = get_temp_path_for(&get_model_path_for(&app, &model_name)?);
+        remove_partial_download(&temp_path)
+            .map_err(|e| format!("Failed to remove partial download: {}", e))?;
+        {
+            let mut map = downloads.inner.lock();
+            if let Some(entry) = map.get_mut(&model_name) {
+                entry.status = DownloadStatus::Cancelled;
+                entry.error = None;
+            }
+        }
+        println!("Removed '{}' from the download queue", model_name);
+        emit_download_event(
+            &app,
+            DownloadEventPayload {
+                model_name: model_name.clone(),
+                status: "cancelled",
+                ..Default::default()
+            },
+        );
+        emit_queue_changed(&app, downloads);
+        return Ok(());
+    }
+
+    let map = downloads.inner.lock();
+    match map.get(&model_name) {
+        Some(entry) if entry.status.is_in_progress() => {
+            entry.cancel_requested.store(true, Ordering::Relaxed);
+            println!("Cancellation requested for '{}'", model_name);
+            Ok(())
+        }
+        _ => Err("No download in progress for this model.".to_string()),
+    }
+}
+
+#[tauri::command]
+fn get_download_queue(downloads: tauri::State<'_, DownloadManager>) -> Vec<QueueEntry> {
+    queue_snapshot(downloads.inner())
+}
+
+// Move a waiting download to `position` in the queue (clamped to the end)
+#[tauri::command]
+fn move_queued_download(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+    model_name: String,
+    position: usize,
+) -> Result<(), String> {
+    {
+        let mut queue = downloads.inner().queue.lock();
+        let index = queue
+            .pending
+            .iter()
+            .position(|item| item.model_name == model_name)
+            .ok_or_else(|| "Model is not waiting in the download queue.".to_string())?;
+        if let Some(item) = queue.pending.remove(index) {
+            let position = position.min(queue.pending.len());
+            queue.pending.insert(position, item);
+        }
+    }
+
+    emit_queue_changed(&app, downloads.inner());
+    Ok(())
+}
+
+// Hold a waiting download in the queue without starting it
+#[tauri::command]
+fn pause_queued_download(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+    model_name: String,
+) -> Result<(), String> {
+    set_queued_download_paused(&app, downloads.inner(), &model_name, true)
+}
+
+#[tauri::command]
+fn resume_queued_download(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+    whisper: tauri::State<'_, WhisperManager>,
+    model_name: String,
+) -> Result<(), String> {
+    set_queued_download_paused(&app, downloads.inner(), &model_name, false)?;
+    pump_download_queue(&app, downloads.inner(), whisper.inner());
+    Ok(())
+}
+
+fn set_queued_download_paused(
+    app: &AppHandle,
+    downloads: &DownloadManager,
+    model_name: &str,
+    paused: bool,
+) -> Result<(), String> {
+    {
+        let mut queue = downloads.queue.lock();
+        let item = queue
+            .pending
+            .iter_mut()
+            .find(|item| item.model_name == model_name)
+            .ok_or_else(|| "Model is not waiting in the download queue.".to_string())?;
+        item.paused = paused;
+    }
+
+    {
+        let mut map = downloads.inner.lock();
+        if let Some(entry) = map.get_mut(model_name) {
+            entry.status = if paused {
+                DownloadStatus::Paused
+            } else {
+                DownloadStatus::Queued
+            };
+        }
+    }
+
+    emit_download_event(
+        app,
+        DownloadEventPayload {
+            model_name: model_name.to_string(),
+            status: if paused { "paused" } else { "queued" },
+            ..Default::default()
+        },
+    );
+    emit_queue_changed(app, downloads);
+    Ok(())
+}
+
+#[tauri::command]
+fn set_max_concurrent_downloads(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+    whisper: tauri::State<'_, WhisperManager>,
+    limit: usize,
+) -> Result<(), String> {
+    if !(1..=MAX_CONCURRENT_DOWNLOADS_LIMIT).contains(&limit) {
+        return Err(format!(
+            "Concurrent downloads must be between 1 and {}.",
+            MAX_CONCURRENT_DOWNLOADS_LIMIT
+        ));
+    }
+
+    update_app_config(&app, |config| {
+        config.max_concurrent_downloads = Some(limit);
+    })?;
+
+    downloads.inner().queue.lock().max_concurrent = limit;
+    pump_download_queue(&app, downloads.inner(), whisper.inner());
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct MirrorSettings {
+    mirrors: Vec<DownloadMirror>,
+    disable_upstream_fallback: bool,
+}
+
+#[tauri::command]
+fn get_download_mirrors(app: tauri::AppHandle) -> Result<MirrorSettings, String> {
+    let config = load_app_config(&app)?;
+    Ok(MirrorSettings {
+        mirrors: config.download_mirrors,
+        disable_upstream_fallback: config.disable_upstream_fallback,
+    })
+}
+
+#[tauri::command]
+fn set_download_mirrors(
+    app: tauri::AppHandle,
+    mut mirrors: Vec<DownloadMirror>,
+    disable_upstream_fallback: bool,
+) -> Result<(), String> {
+    for mirror in &mut mirrors {
+        validate_download_mirror(mirror)?;
+    }
+    if mirrors.is_empty() && disable_upstream_fallback {
+        return Err("Add at least one mirror before disabling the upstream fallback.".to_string());
+    }
+
+    update_app_config(&app, |config| {
+        config.download_mirrors = mirrors;
+        config.disable_upstream_fallback = disable_upstream_fallback;
+    })?;
+    Ok(())
+}
+
+#[tauri::command]
+fn get_network_settings(app: tauri::AppHandle) -> Result<NetworkSettings, String> {
+    Ok(load_app_config(&app)?.network)
+}
+
+#[tauri::command]
+fn set_network_settings(
+    app: tauri::AppHandle,
+    http: tauri::State<'_, HttpClients>,
+    settings: NetworkSettings,
+) -> Result<(), String> {
+    // Build first so invalid proxies or certificates are rejected before anything is saved
+    let clients = HttpClientSet::build(&settings)?;
+
+    update_app_config(&app, |config| {
+        config.network = settings;
+    })?;
+
+    // Downloads already running keep their snapshot; new ones pick up the new clients
+    *http.inner().inner.lock() = clients;
+    Ok(())
+}
+
+// Re-hash a downloaded model and compare it with the catalog, recorded or advertised checksum
+#[tauri::command]
+async fn verify_model(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+    http: tauri::State<'_, HttpClients>,
+    model_name: String,
+) -> Result<ModelVerification, String> {
+    let model_info =
+        find_model_info(&app, &model_name).ok_or_else(|| "Unknown model name".to_string())?;
+
+    {
+        let map = downloads.inner().inner.lock();
+        if let Some(entry) = map.get(&model_name) {
+            if entry.status.is_in_progress() {
+                return Err("Download in progress. Please wait for it to finish.".to_string());
+            }
+        }
+    }
+
+    let model_path = get_model_path_for(&app, &model_name)?;
+    if !model_path.exists() {
+        return Err("Model not downloaded.".to_string());
+    }
+
+    let config = load_app_config(&app).unwrap_or_default();
+    let recorded_sha256 = config.model_checksums.get(&model_name).cloned();
+    let sources = model_info
+        .url
+        .as_deref()
+        .map(|url| resolve_download_sources(&config, url))
+        .unwrap_or_default();
+    let client = http.inner().snapshot().client;
+
+    // Hashing and the blocking HTTP client must both stay off the async runtime
+    let (expected_sha256, sha256) = tauri::async_runtime::spawn_blocking(move || {
+        let expected_sha256 = expected_model_sha256(&model_info, || {
+            recorded_sha256.clone().or_else(|| {
+                sources
+                    .iter()
+                    .find_map(|source| fetch_advertised_sha256(&client, source))
+            })
+        })
+        .or(recorded_sha256)
+        .ok_or_else(|| "No reference checksum available for this model.".to_string())?;
+        let sha256 =
+            compute_sha256(&model_path).map_err(|e| format!("Failed to read model file: {}", e))?;
+        Ok::<_, String>((expected_sha256, sha256))
+    })
+    .await
+    .map_err(|e| format!("Verification failed: {}", e))??;
+    let verified = sha256 == expected_sha256;
+    let model_path = get_model_path_for(&app, &model_name)?;
+
+    let (downloaded_bytes, total_bytes) = match std::fs::metadata(&model_path) {
+        Ok(meta) => (meta.len(), Some(meta.len())),
+        Err(_) => (0, None),
+    };
+
+    if verified {
+        {
+            let mut map = downloads.inner().inner.lock();
+            if let Some(entry) = map.get_mut(&model_name) {
+                if entry.status == DownloadStatus::VerificationFailed {
+                    entry.status = DownloadStatus::Completed;
+                    entry.error = None;
+                }
+            }
+        }
+        record_model_checksum(&app, &model_name, &sha256);
+    } else {
+        let message = ChecksumMismatchError {
+            expected: expected_sha256.clone(),
+            actual: sha256.clone(),
+        }
+        .to_string();
+        {
+            let mut map = downloads.inner().inner.lock();
+            let entry = map
+                .entry(model_name.clone())
+                .or_insert_with(|| DownloadRecord::new(DownloadStatus::VerificationFailed));
+            entry.status = DownloadStatus::VerificationFailed;
+            entry.downloaded_bytes = downloaded_bytes;
+            entry.total_bytes = total_bytes;
+            entry.error = Some(message.clone());
+        }
+
+        emit_download_event(
+            &app,
+            DownloadEventPayload {
+                model_name: model_name.clone(),
+                downloaded_bytes,
+                total_bytes,
+                percent: None,
+                status: "verification_failed",
+                error: Some(message),
+                ..Default::default()
+            },
+        );
+    }
+
+    Ok(ModelVerification {
+        model_name,
+        sha256,
+        expected_sha256,
+        verified,
+    })
+}
 
-            // Create the audio stream based on sample format with buffering
-            let buffer_clone = self.buffer.clone();
-            let temp_buffer_clone = self.temp_buffer.clone();
-            let record_sample_rate = self.sample_rate;
+#[derive(Serialize)]
+struct ModelsDiskUsage {
+    models_dir: PathBuf,
+    used_bytes: u64,
+    available_bytes: Option<u64>,
+    total_bytes: Option<u64>,
+}
 
-            let stream = match config.sample_format() {
-                cpal::SampleFormat::F32 => build_input_stream::<f32>(
-                    &device,
-                    &config.into(),
-                    buffer_clone,
-                    temp_buffer_clone,
-                    record_sample_rate,
-                )?,
-                cpal::SampleFormat::I16 => build_input_stream::<i16>(
-                    &device,
-                    &config.into(),
-                    buffer_clone,
-                    temp_buffer_clone,
-                    record_sample_rate,
-                )?,
-                cpal::SampleFormat::U16 => build_input_stream::<u16>(
-                    &device,
-                    &config.into(),
-                    buffer_clone,
-                    temp_buffer_clone,
-                    record_sample_rate,
-                )?,
-                _ => return Err("Unsupported sample format".into()),
-            };
+// Space taken by the models folder (including partial downloads) and free space on its volume
+#[tauri::command]
+fn get_models_disk_usage(app: tauri::AppHandle) -> Result<ModelsDiskUsage, String> {
+    let models_dir = get_model_base_path(&app)?;
+    let used_bytes = std::fs::read_dir(&models_dir)
+        .map_err(|e| format!("Failed to read models folder: {}", e))?
+        .filter_map(|entry| entry.ok()?.metadata().ok())
+        .filter(|meta| meta.is_file())
+        .map(|meta| meta.len())
+        .sum();
+    let space = disk_space(&models_dir)
+        .map_err(|err| eprintln!("Could not determine free space: {}", err))
+        .ok();
+
+    Ok(ModelsDiskUsage {
+        models_dir,
+        used_bytes,
+        available_bytes: space.as_ref().map(|space| space.available),
+        total_bytes: space.as_ref().map(|space| space.total),
+    })
+}
 
-            stream.play()?;
-            self.stream = Some(stream);
-        }
-        Ok(())
+#[tauri::command]
+fn get_storage_report(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+) -> Result<StorageReport, String> {
+    scan_models_storage(&app, downloads.inner())
+}
+
+// Delete the given files from the storage report. The folder is re-scanned first so files of
+// downloads started since, or files that are no longer reported, are never touched.
+#[tauri::command]
+fn purge_storage(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+    file_names: Vec<String>,
+) -> Result<StorageReport, String> {
+    if file_names.is_empty() {
+        return Err("Choose the files to delete.".to_string());
+    }
+    if app
+        .try_state::<ModelsDirectory>()
+        .is_some_and(|dir| dir.migrating.load(Ordering::SeqCst))
+    {
+        return Err("The models folder is being moved. Try again when it is done.".to_string());
     }
+    let report = scan_models_storage(&app, downloads.inner())?;
+    let models_dir = get_model_base_path(&app)?;
 
-    fn stop(&mut self) -> Vec<f32> {
-        if let Some(stream) = self.stream.take() {
-            drop(stream);
-            println!("Audio capture stopped - microphone released");
+    let mut purged_bytes = 0;
+    for entry in report.stale_temp_files.iter().chain(&report.orphan_files) {
+        if !file_names.contains(&entry.file_name) {
+            continue;
+        }
+        match std::fs::remove_file(models_dir.join(&entry.file_name)) {
+            Ok(()) => purged_bytes += entry.size_bytes,
+            Err(err) => eprintln!("Failed to delete {}: {}", entry.file_name, err),
         }
+    }
+    println!(
+        "Purged {} from the models folder",
+        format_bytes(purged_bytes)
+    );
 
-        // Get the buffered audio (already resampled to 16kHz in real-time) and clear
-        let audio_data = self.buffer.lock().clone();
-        self.buffer.lock().clear();
-        self.temp_buffer.lock().clear();
+    scan_models_storage(&app, downloads.inner())
+}
 
-        println!(
-            "Captured {} samples at 16kHz (recorded at {}Hz)",
-            audio_data.len(),
-            self.sample_rate
-        );
+// Move all catalog models to `path` (or back to the default folder when `None`) and store it as
+// the models folder. Nothing changes unless every file was moved successfully.
+#[tauri::command]
+async fn set_models_directory(
+    app: tauri::AppHandle,
+    downloads: tauri::State<'_, DownloadManager>,
+    models_dir: tauri::State<'_, ModelsDirectory>,
+    path: Option<String>,
+) -> Result<PathBuf, String> {
+    let default_dir = get_default_models_path(&app)?;
+    let target_dir = match path {
+        Some(path) => {
+            let path = PathBuf::from(path);
+            if !path.is_absolute() {
+                return Err("The models folder must be an absolute path.".to_string());
+            }
+            path
+        }
+        None => default_dir.clone(),
+    };
+    ensure_writable_dir(&target_dir)?;
 
-        audio_data
+    let source_dir = get_model_base_path(&app)?;
+    let unchanged = match (source_dir.canonicalize(), target_dir.canonicalize()) {
+        (Ok(source), Ok(target)) => source == target,
+        _ => false,
+    };
+    if !unchanged && target_dir.starts_with(&source_dir) {
+        return Err("The new folder cannot be inside the current models folder.".to_string());
     }
-}
 
-fn build_input_stream<T>(
-    device: &cpal::Device,
-    config: &cpal::StreamConfig,
-    buffer: Arc<Mutex<Vec<f32>>>,
-    temp_buffer: Arc<Mutex<Vec<f32>>>,
-    sample_rate: u32,
-) -> Result<cpal::Stream, Box<dyn std::error::Error>>
-where
-    T: cpal::Sample + cpal::SizedSample,
-    f32: FromSample<T>,
-{
-    let stream = device.build_input_stream(
-        config,
-        move |data: &[T], _: &cpal::InputCallbackInfo| {
-            // Convert samples to f32
-            let mut temp = temp_buffer.lock();
-            for &sample in data {
-                temp.push(f32::from_sample(sample));
-            }
-
-            // If recording at 48kHz, downsample to 16kHz in real-time
-            if sample_rate == 48000 && temp.len() >= 3 {
-                let mut buf = buffer.lock();
-                // Simple decimation: take every 3rd sample (48000/3 = 16000)
-                for i in (0..temp.len()).step_by(3) {
-                    if let Some(&sample) = temp.get(i) {
-                        buf.push(sample);
-                    }
-                }
-                temp.clear();
-            } else if sample_rate == 16000 {
-                // Already 16kHz, just copy directly
-                let mut buf = buffer.lock();
-                buf.extend_from_slice(&temp);
-                temp.clear();
+    if models_dir.inner().migrating.swap(true, Ordering::SeqCst) {
+        return Err("The models folder is already being moved.".to_string());
+    }
+    let busy = downloads
+        .inner()
+        .inner
+        .lock()
+        .values()
+        .any(|entry| entry.status.is_in_progress());
+    if busy {
+        models_dir.inner().migrating.store(false, Ordering::SeqCst);
+        return Err("Wait for downloads to finish before moving the models folder.".to_string());
+    }
+
+    let result = if unchanged {
+        Ok(Vec::new())
+    } else {
+        let migration_app = app.clone();
+        let (source, target) = (source_dir.clone(), target_dir.clone());
+        tauri::async_runtime::spawn_blocking(move || {
+            migrate_models_dir(&migration_app, &source, &target)
+        })
+        .await
+        .map_err(|e| format!("Models folder migration failed: {}", e))
+        .and_then(|result| result)
+    };
+
+    let outcome = result.and_then(|migrated| {
+        let stored_dir = (target_dir != default_dir).then(|| target_dir.clone());
+        let saved = update_app_config(&app, |config| config.models_dir = stored_dir.clone());
+        if let Err(err) = saved {
+            rollback_migration(&migrated);
+            return Err(err);
+        }
+        *models_dir.inner().custom.lock() = stored_dir;
+
+        // Copies leave the originals behind until the new folder is in use
+        for file in migrated.iter().filter(|file| !file.renamed) {
+            if let Err(err) = std::fs::remove_file(&file.source) {
+                eprintln!("Failed to remove old model file {:?}: {}", file.source, err);
             }
-        },
-        |err| eprintln!("Audio stream error: {}", err),
-        None,
-    )?;
+        }
+        Ok(())
+    });
+    models_dir.inner().migrating.store(false, Ordering::SeqCst);
+    outcome?;
 
-    Ok(stream)
+    println!("Models folder is now {:?}", target_dir);
+    wake_model_watchdog(&app);
+    let _ = app.emit(
+        "models-migration-progress",
+        MigrationEventPayload {
+            status: "completed",
+            ..Default::default()
+        },
+    );
+    Ok(target_dir)
 }
 
-// Insert text at cursor position using clipboard save/restore + paste
-fn insert_text_at_cursor(app: &AppHandle, text: &str) -> Result<(), Box<dyn std::error::Error>> {
-    // Save current clipboard content
-    let original_clipboard = app.clipboard().read_text().ok();
+#[tauri::command]
+fn get_model_watchdog_policy(app: tauri::AppHandle) -> Result<ModelWatchdogPolicy, String> {
+    Ok(load_app_config(&app)?.model_watchdog)
+}
 
-    // Write our text to clipboard
-    app.clipboard().write_text(text)?;
+#[tauri::command]
+fn set_model_watchdog_policy(
+    app: tauri::AppHandle,
+    policy: ModelWatchdogPolicy,
+) -> Result<(), String> {
+    update_app_config(&app, |config| {
+        config.model_watchdog = policy;
+    })?;
+    wake_model_watchdog(&app);
+    Ok(())
+}
 
-    // Wait for clipboard to update and for user to release modifier keys
-    std::thread::sleep(std::time::Duration::from_millis(100));
+// The model the watchdog policy wants present, unless it is already there, being downloaded,
+// or was removed or cancelled by the user
+fn model_watchdog_target(
+    app: &AppHandle,
+    downloads: &DownloadManager,
+    whisper: &WhisperManager,
+) -> Option<String> {
+    let config = load_app_config(app).ok()?;
+    let model_name = match config.model_watchdog {
+        ModelWatchdogPolicy::Off => return None,
+        ModelWatchdogPolicy::EnsureSelected => whisper
+            .inner
+            .lock()
+            .current_model
+            .clone()
+            .or(config.selected_model)?,
+        ModelWatchdogPolicy::EnsureDefault => DEFAULT_MODEL.to_string(),
+    };
 
-    // Release Option key if it's still held (to avoid Cmd+Option+V)
-    let mut enigo = Enigo::new(&Settings::default())?;
-    enigo.key(Key::Alt, enigo::Direction::Release)?;
+    if config.removed_models.contains(&model_name)
+        || find_model_info(app, &model_name).is_none()
+        || model_exists_for(app, &model_name)
+    {
+        return None;
+    }
 
-    // Small delay after releasing Option
-    std::thread::sleep(std::time::Duration::from_millis(50));
+    let skip = downloads
+        .inner
+        .lock()
+        .get(&model_name)
+        .map(|entry| entry.status.is_in_progress() || entry.status == DownloadStatus::Cancelled)
+        .unwrap_or(false);
+    (!skip).then_some(model_name)
+}
 
-    // Simulate Cmd+V to paste
-    enigo.key(Key::Meta, enigo::Direction::Press)?;
-    enigo.key(Key::Unicode('v'), enigo::Direction::Click)?;
-    enigo.key(Key::Meta, enigo::Direction::Release)?;
+fn run_model_watchdog(
+    app: AppHandle,
+    downloads: DownloadManager,
+    whisper: WhisperManager,
+    wake: mpsc::Receiver<()>,
+) {
+    let mut interval = WATCHDOG_MIN_INTERVAL;
+    loop {
+        match wake.recv_timeout(interval) {
+            Ok(()) => {
+                // Collapse a burst of wake-ups into one check
+                while wake.try_recv().is_ok() {}
+                interval = WATCHDOG_MIN_INTERVAL;
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                interval = (interval * 2).min(WATCHDOG_MAX_INTERVAL);
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
 
-    // Wait a bit for paste to complete
-    std::thread::sleep(std::time::Duration::from_millis(50));
+        let Some(model_name) = model_watchdog_target(&app, &downloads, &whisper) else {
+            continue;
+        };
 
-    // Restore original clipboard content
-    if let Some(original) = original_clipboard {
-        let _ = app.clipboard().write_text(original);
+        println!(
+            "Model '{}' missing, starting automatic download...",
+            model_name
+        );
+        match spawn_model_download(
+            &app,
+            downloads.clone(),
+            whisper.clone(),
+            model_name.clone(),
+            false,
+        ) {
+            Ok(()) => println!("Automatic download of '{}' started", model_name),
+            Err(err) => eprintln!("Failed to auto-download missing model: {}", err),
+        }
     }
+}
 
-    println!("Inserted text via clipboard (restored original)");
+#[tauri::command]
+fn set_streaming_transcription(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    update_app_config(&app, |config| {
+        config.streaming_transcription = enabled;
+    })?;
+    Ok(())
+}
+
+#[tauri::command]
+fn get_audio_settings(app: tauri::AppHandle) -> Result<AudioSettings, String> {
+    Ok(load_app_config(&app)?.audio)
+}
 
+// Takes effect from the next recording
+#[tauri::command]
+fn set_audio_settings(app: tauri::AppHandle, settings: AudioSettings) -> Result<(), String> {
+    update_app_config(&app, |config| {
+        config.audio = settings;
+    })?;
     Ok(())
 }
 
-// Tauri command to get list of downloaded models
 #[tauri::command]
-fn get_downloaded_models(app: tauri::AppHandle) -> Vec<String> {
-    get_available_models()
-        .into_iter()
-        .filter(|model| model_exists_for(&app, model.name))
-        .map(|model| model.name.to_string())
-        .collect()
+fn get_input_devices() -> Result<Vec<InputDeviceInfo>, String> {
+    list_input_devices().map_err(|e| format!("Failed to list input devices: {}", e))
 }
 
+// `None` follows the system default device
 #[tauri::command]
-fn get_model_statuses(
-    app: tauri::AppHandle,
-    downloads: tauri::State<'_, DownloadManager>,
-    whisper: tauri::State<'_, WhisperManager>,
-) -> Vec<ModelStatus> {
-    gather_model_statuses(&app, downloads.inner(), whisper.inner())
+fn set_input_device(app: tauri::AppHandle, name: Option<String>) -> Result<(), String> {
+    update_app_config(&app, |config| {
+        config.audio.input_device = name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty());
+    })?;
+    Ok(())
 }
 
+// Languages whisper.cpp can transcribe, in its own order (English first)
 #[tauri::command]
-fn start_model_download(
-    app: tauri::AppHandle,
-    downloads: tauri::State<'_, DownloadManager>,
-    whisper: tauri::State<'_, WhisperManager>,
-    model_name: String,
-) -> Result<(), String> {
-    spawn_model_download(
-        &app,
-        downloads.inner().clone(),
-        whisper.inner().clone(),
-        model_name,
-        false,
-    )
+fn get_supported_languages() -> Vec<SupportedLanguage> {
+    (0..=whisper_rs::get_lang_max_id())
+        .filter_map(|id| {
+            Some(SupportedLanguage {
+                code: whisper_rs::get_lang_str(id)?.to_string(),
+                name: whisper_rs::get_lang_str_full(id)?.to_string(),
+            })
+        })
+        .collect()
 }
 
 #[tauri::command]
-fn refresh_model_download(
+fn get_language_settings(app: tauri::AppHandle) -> Result<LanguageSettings, String> {
+    Ok(load_app_config(&app)?.languages)
+}
+
+#[tauri::command]
+fn set_language_settings(
     app: tauri::AppHandle,
-    downloads: tauri::State<'_, DownloadManager>,
-    whisper: tauri::State<'_, WhisperManager>,
-    model_name: String,
+    mut settings: LanguageSettings,
 ) -> Result<(), String> {
-    // Prevent refresh while download already in progress
-    {
-        let map = downloads.inner().inner.lock();
-        if let Some(entry) = map.get(&model_name) {
-            if entry.status == DownloadStatus::Downloading {
-                return Err("Download already in progress".to_string());
-            }
+    settings.language = settings
+        .language
+        .map(|code| code.trim().to_ascii_lowercase())
+        .filter(|code| !code.is_empty() && code != "auto");
+    if let Some(code) = &settings.language {
+        if whisper_rs::get_lang_id(code).is_none() {
+            return Err(format!("Unsupported language '{}'.", code));
         }
     }
 
-    spawn_model_download(
-        &app,
-        downloads.inner().clone(),
-        whisper.inner().clone(),
-        model_name,
-        true,
-    )
+    update_app_config(&app, |config| {
+        config.languages = settings;
+    })?;
+    Ok(())
 }
 
 #[tauri::command]
-fn remove_model(
-    app: tauri::AppHandle,
-    downloads: tauri::State<'_, DownloadManager>,
-    whisper: tauri::State<'_, WhisperManager>,
-    model_name: String,
-) -> Result<(), String> {
-    println!("remove_model called for {}", model_name);
-    let is_active = {
-        let runtime = whisper.inner().inner.lock();
-        runtime
-            .current_model
-            .as_ref()
-            .map(|current| current == &model_name)
-            .unwrap_or(false)
-    };
+fn get_prompt_settings(app: tauri::AppHandle) -> Result<PromptSettings, String> {
+    Ok(load_app_config(&app)?.prompts)
+}
 
-    if is_active {
-        println!("remove_model abort: {} is active", model_name);
-        return Err(
-            "Model is currently active. Switch to another model before removing.".to_string(),
-        );
-    }
+#[tauri::command]
+fn set_prompt_settings(app: tauri::AppHandle, mut settings: PromptSettings) -> Result<(), String> {
+    settings.normalize()?;
+    update_app_config(&app, |config| {
+        config.prompts = settings;
+    })?;
+    Ok(())
+}
 
-    {
-        let map = downloads.inner().inner.lock();
-        if let Some(entry) = map.get(&model_name) {
-            if entry.status == DownloadStatus::Downloading {
-                return Err("Download in progress. Please wait for it to finish.".to_string());
-            }
-        }
-    }
+#[tauri::command]
+fn set_initial_prompt(app: tauri::AppHandle, prompt: String) -> Result<(), String> {
+    update_app_config(&app, |config| {
+        config.prompts.initial_prompt = prompt.trim().to_string();
+    })?;
+    Ok(())
+}
+
+#[tauri::command]
+fn add_vocabulary_word(app: tauri::AppHandle, word: String) -> Result<Vec<String>, String> {
+    let config = update_app_config(&app, |config| {
+        config.prompts.vocabulary.push(word);
+        normalize_vocabulary(&mut config.prompts.vocabulary);
+    })?;
+    Ok(config.prompts.vocabulary)
+}
+
+#[tauri::command]
+fn remove_vocabulary_word(app: tauri::AppHandle, word: String) -> Result<Vec<String>, String> {
+    let config = update_app_config(&app, |config| {
+        config
+            .prompts
+            .vocabulary
+            .retain(|existing| existing != word.trim());
+    })?;
+    Ok(config.prompts.vocabulary)
+}
+
+#[tauri::command]
+fn get_decoding_settings(app: tauri::AppHandle) -> Result<DecodingSettings, String> {
+    Ok(load_app_config(&app)?.decoding)
+}
+
+// Values of a preset, so the settings UI can show what it will use
+#[tauri::command]
+fn get_decoding_preset(preset: DecodingPreset) -> DecodingSettings {
+    DecodingSettings::for_preset(preset)
+}
+
+#[tauri::command]
+fn set_decoding_settings(app: tauri::AppHandle, settings: DecodingSettings) -> Result<(), String> {
+    settings.validate()?;
+    update_app_config(&app, |config| {
+        config.decoding = settings;
+    })?;
+    Ok(())
+}
 
-    let model_path = get_model_path_for(&app, &model_name);
-    if model_path.exists() {
-        std::fs::remove_file(&model_path).map_err(|e| format!("Failed to remove model: {}", e))?;
-        println!("Removed file: {:?}", model_path);
-    } else {
-        println!("remove_model abort: file not found for {}", model_name);
-        return Err("Model file not found.".to_string());
-    }
+#[tauri::command]
+fn get_vad_settings(app: tauri::AppHandle) -> Result<VadSettings, String> {
+    Ok(load_app_config(&app)?.vad)
+}
 
-    {
-        let mut map = downloads.inner().inner.lock();
-        map.remove(&model_name);
-    }
+#[tauri::command]
+fn set_vad_settings(app: tauri::AppHandle, settings: VadSettings) -> Result<(), String> {
+    settings.validate()?;
+    update_app_config(&app, |config| {
+        config.vad = settings;
+    })?;
+    Ok(())
+}
 
-    emit_download_event(
-        &app,
-        DownloadEventPayload {
-            model_name: model_name.clone(),
-            downloaded_bytes: 0,
-            total_bytes: None,
-            percent: None,
-            status: "removed",
-            error: None,
-        },
-    );
+#[tauri::command]
+fn get_hands_free_settings(app: tauri::AppHandle) -> Result<HandsFreeSettings, String> {
+    Ok(load_app_config(&app)?.hands_free)
+}
 
+// Takes effect the next time hands-free mode is turned on
+#[tauri::command]
+fn set_hands_free_settings(
+    app: tauri::AppHandle,
+    settings: HandsFreeSettings,
+) -> Result<(), String> {
+    settings.validate()?;
+    update_app_config(&app, |config| {
+        config.hands_free = settings;
+    })?;
     Ok(())
 }
 
@@ -1027,7 +5503,7 @@ async fn switch_model(
     {
         let map = downloads.inner().inner.lock();
         if let Some(entry) = map.get(&model_name) {
-            if entry.status == DownloadStatus::Downloading {
+            if entry.status.is_in_progress() {
                 return Err("Model is still downloading.".to_string());
             }
         }
@@ -1037,11 +5513,14 @@ async fn switch_model(
         return Err("Model not downloaded.".to_string());
     }
 
-    let ctx = load_whisper_model_for(&app, &model_name).map_err(|e| e.to_string())?;
+    let engine = whisper
+        .inner()
+        .load_engine_for(&app, &model_name)
+        .map_err(|e| e.to_string())?;
 
     {
         let mut runtime = whisper.inner().inner.lock();
-        runtime.context = Some(ctx);
+        runtime.engine = Some(engine);
         runtime.current_model = Some(model_name.clone());
     }
 
@@ -1051,7 +5530,10 @@ async fn switch_model(
             entry.status = DownloadStatus::Completed;
             entry.error = None;
             if entry.total_bytes.is_none() {
-                if let Ok(meta) = std::fs::metadata(get_model_path_for(&app, &model_name)) {
+                if let Some(meta) = get_model_path_for(&app, &model_name)
+                    .ok()
+                    .and_then(|path| std::fs::metadata(path).ok())
+                {
                     let len = meta.len();
                     entry.total_bytes = Some(len);
                     entry.downloaded_bytes = len;
@@ -1077,10 +5559,12 @@ async fn switch_model(
             percent: Some(100.0),
             status: "active",
             error: None,
+            ..Default::default()
         },
     );
 
     persist_selected_model(&app, &model_name);
+    wake_model_watchdog(&app);
 
     let _ = app.emit(
         "active-model-changed",
@@ -1125,33 +5609,75 @@ pub fn run() {
     // Create audio recorder wrapped in Arc<Mutex>
     let audio_recorder = Arc::new(Mutex::new(AudioRecorder::new()));
     let audio_recorder_clone = audio_recorder.clone();
+    let streaming_session: Arc<Mutex<Option<StreamingSession>>> = Arc::new(Mutex::new(None));
+    let hands_free_session: Arc<Mutex<Option<HandsFreeSession>>> = Arc::new(Mutex::new(None));
+    let target_app_lookup: Arc<Mutex<Option<FrontmostAppLookup>>> = Arc::new(Mutex::new(None));
 
     let download_manager = DownloadManager::default();
     let whisper_manager = WhisperManager::default();
+    let (watchdog_wake, watchdog_receiver) = mpsc::channel();
+    let (transcription_sender, transcription_receiver) = mpsc::channel();
+    let transcription_queue = TranscriptionQueue::new(transcription_sender);
+    let http_clients = HttpClients::new(
+        HttpClientSet::build(&NetworkSettings::default()).expect("Failed to build HTTP client"),
+    );
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(
             tauri_plugin_global_shortcut::Builder::new()
-                .with_shortcuts(["alt+space", "ctrl+alt+space"])
+                .with_shortcuts(["alt+space", "ctrl+alt+space", "alt+shift+space"])
                 .expect("Failed to register shortcuts")
                 .with_handler(move |app, shortcut, event| {
                     if shortcut.matches(Modifiers::ALT, Code::Space)
                         || shortcut.matches(Modifiers::ALT | Modifiers::CONTROL, Code::Space)
                     {
-                        if let Some(tray) = app.tray_by_id(TRAY_ID) {
+                        if hands_free_session.lock().is_some() {
+                            // The microphone belongs to hands-free mode until it is turned off
+                            if event.state == ShortcutState::Pressed {
+                                println!("Hands-free mode is on - ignoring Option+Space");
+                            }
+                        } else if let Some(tray) = app.tray_by_id(TRAY_ID) {
                             match event.state {
                                 ShortcutState::Pressed => {
+                                    let queue: tauri::State<TranscriptionQueue> = app.state();
+                                    queue.inner().set_hotkey_held(true);
+
                                     // Switch to active icon
                                     let icon = active_icon_clone.lock();
                                     let _ = tray.set_icon(Some(icon.clone()));
 
                                     // Start audio capture
+                                    let audio_settings = load_app_config(app)
+                                        .map(|config| config.audio)
+                                        .unwrap_or_default();
                                     let mut recorder = audio_recorder_clone.lock();
-                                    match recorder.start() {
+                                    match recorder.start(&audio_settings) {
                                         Ok(_) => {
-                                            println!("Option+Space pressed - recording started")
+                                            println!("Option+Space pressed - recording started");
+
+                                            let streaming = load_app_config(app)
+                                                .map(|config| config.streaming_transcription)
+                                                .unwrap_or(false);
+                                            // The app being dictated into is frontmost now, not
+                                            // necessarily by the time the clip is transcribed
+                                            *target_app_lookup.lock() = if streaming {
+                                                None
+                                            } else {
+                                                FrontmostAppLookup::start(app)
+                                            };
+
+                                            let mut session = streaming_session.lock();
+                                            if streaming && session.is_none() {
+                                                let whisper_state: tauri::State<WhisperManager> =
+                                                    app.state();
+                                                *session = Some(StreamingSession::start(
+                                                    app.clone(),
+                                                    whisper_state.inner().clone(),
+                                                    recorder.live_buffer(),
+                                                ));
+                                            }
                                         }
                                         Err(e) => eprintln!("Failed to start audio capture: {}", e),
                                     }
@@ -1161,55 +5687,77 @@ pub fn run() {
                                     let icon = default_icon_clone.lock();
                                     let _ = tray.set_icon(Some(icon.clone()));
 
+                                    // Stop the rolling passes before the next press can refill the
+                                    // recorder's buffer
+                                    let stream = streaming_session.lock().take();
+                                    if let Some(stream) = &stream {
+                                        stream.request_stop();
+                                    }
+
                                     // Stop audio capture and get buffered audio
                                     let mut recorder = audio_recorder_clone.lock();
                                     let audio_samples = recorder.stop();
                                     println!("Option+Space released - recording stopped");
 
-                                    // Calculate audio duration in seconds
-                                    let duration_secs = if !audio_samples.is_empty() {
-                                        audio_samples.len() as f32 / 16000.0 // Always 16kHz after resampling
-                                    } else {
-                                        0.0
-                                    };
-
-                                    // Transcribe audio using Whisper
-                                    let whisper_state: tauri::State<WhisperManager> = app.state();
-                                    let transcription = {
-                                        let mut runtime = whisper_state.inner().inner.lock();
-                                        let model_name =
-                                            runtime.current_model.clone().unwrap_or_default();
-                                        if let Some(ctx) = runtime.context.as_mut() {
-                                            match transcribe_audio(ctx, &audio_samples, &model_name)
-                                            {
-                                                Ok(text) => text,
-                                                Err(e) => {
-                                                    eprintln!("Transcription failed: {}", e);
-                                                    String::from("[Transcription failed]")
-                                                }
-                                            }
-                                        } else {
-                                            String::from("[Model not loaded]")
-                                        }
-                                    };
-
-                                    // Insert transcribed text only if not empty
-                                    if !transcription.is_empty()
-                                        && transcription != "[Model not loaded]"
-                                        && transcription != "[Transcription failed]"
-                                    {
-                                        match insert_text_at_cursor(app, &transcription) {
-                                            Ok(_) => println!(
-                                                "Inserted transcription ({:.2}s): {}",
-                                                duration_secs, transcription
-                                            ),
-                                            Err(e) => eprintln!("Failed to insert text: {}", e),
+                                    // Hand the clip to the transcription worker
+                                    let queue: tauri::State<TranscriptionQueue> = app.state();
+                                    let target_app = target_app_lookup.lock().take();
+                                    queue.inner().enqueue(app, audio_samples, stream, target_app);
+                                    queue.inner().set_hotkey_held(false);
+                                }
+                            }
+                        }
+                    } else if shortcut.matches(Modifiers::ALT | Modifiers::SHIFT, Code::Space)
+                        && event.state == ShortcutState::Pressed
+                    {
+                        if let Some(tray) = app.tray_by_id(TRAY_ID) {
+                            let mut hands_free = hands_free_session.lock();
+                            let mut recorder = audio_recorder_clone.lock();
+                            match hands_free.take() {
+                                Some(session) => {
+                                    session.finish();
+                                    let remaining = recorder.stop();
+                                    let _ = tray.set_icon(Some(default_icon_clone.lock().clone()));
+                                    println!("Option+Shift+Space pressed - hands-free mode off");
+
+                                    // Transcribe an utterance cut short by turning the mode off
+                                    let vad = load_app_config(app)
+                                        .map(|config| config.vad)
+                                        .unwrap_or_default();
+                                    if detect_speech(&remaining, &vad).is_some() {
+                                        let queue: tauri::State<TranscriptionQueue> = app.state();
+                                        queue.inner().enqueue(
+                                            app,
+                                            remaining,
+                                            None,
+                                            FrontmostAppLookup::start(app),
+                                        );
+                                    }
+                                }
+                                None if recorder.is_recording() => {
+                                    println!("Recording in progress - release Option+Space first");
+                                }
+                                None => {
+                                    let audio_settings = load_app_config(app)
+                                        .map(|config| config.audio)
+                                        .unwrap_or_default();
+                                    match recorder.start(&audio_settings) {
+                                        Ok(_) => {
+                                            *hands_free = Some(HandsFreeSession::start(
+                                                app.clone(),
+                                                recorder.live_buffer(),
+                                            ));
+                                            let _ = tray
+                                                .set_icon(Some(active_icon_clone.lock().clone()));
+                                            println!(
+                                                "Option+Shift+Space pressed - hands-free mode on"
+                                            );
                                         }
-                                    } else {
-                                        println!("No text to insert ({})", transcription);
+                                        Err(e) => eprintln!("Failed to start audio capture: {}", e),
                                     }
                                 }
                             }
+                            let _ = app.emit("hands-free-changed", hands_free.is_some());
                         }
                     }
                 })
@@ -1217,14 +5765,62 @@ pub fn run() {
         )
         .manage(download_manager.clone())
         .manage(whisper_manager.clone())
+        .manage(ModelCatalog::default())
+        .manage(http_clients)
+        .manage(ModelsDirectory::default())
+        .manage(ModelWatchdog {
+            wake: Mutex::new(watchdog_wake),
+        })
+        .manage(transcription_queue.clone())
         .invoke_handler(tauri::generate_handler![
             greet,
             switch_model,
             get_downloaded_models,
+            get_model_catalog,
+            add_custom_model,
+            remove_custom_model,
+            import_model_file,
             get_model_statuses,
             start_model_download,
             refresh_model_download,
+            cancel_model_download,
+            get_download_queue,
+            move_queued_download,
+            pause_queued_download,
+            resume_queued_download,
+            set_max_concurrent_downloads,
+            get_download_mirrors,
+            set_download_mirrors,
+            get_network_settings,
+            set_network_settings,
             remove_model,
+            verify_model,
+            get_models_disk_usage,
+            get_storage_report,
+            purge_storage,
+            set_models_directory,
+            get_model_watchdog_policy,
+            set_model_watchdog_policy,
+            set_streaming_transcription,
+            get_audio_settings,
+            set_audio_settings,
+            get_input_devices,
+            set_input_device,
+            get_vad_settings,
+            set_vad_settings,
+            get_hands_free_settings,
+            set_hands_free_settings,
+            get_supported_languages,
+            get_language_settings,
+            set_language_settings,
+            get_prompt_settings,
+            set_prompt_settings,
+            set_initial_prompt,
+            add_vocabulary_word,
+            remove_vocabulary_word,
+            get_decoding_settings,
+            get_decoding_preset,
+            set_decoding_settings,
             open_models_folder
         ])
         .setup(|app| {
@@ -1236,18 +5832,72 @@ pub fn run() {
             let whisper_state: tauri::State<WhisperManager> = app.state();
             let app_handle = app.handle();
 
+            if let Some(limit) = load_app_config(app_handle)
+                .ok()
+                .and_then(|config| config.max_concurrent_downloads)
+            {
+                download_state.inner().queue.lock().max_concurrent =
+                    limit.clamp(1, MAX_CONCURRENT_DOWNLOADS_LIMIT);
+            }
+
+            if let Some(dir) = load_app_config(app_handle)
+                .ok()
+                .and_then(|config| config.models_dir)
+            {
+                let models_dir: tauri::State<ModelsDirectory> = app.state();
+                *models_dir.inner().custom.lock() = Some(dir);
+            }
+
+            let network = load_app_config(app_handle)
+                .map(|config| config.network)
+                .unwrap_or_default();
+            match HttpClientSet::build(&network) {
+                Ok(clients) => {
+                    let http_state: tauri::State<HttpClients> = app.state();
+                    *http_state.inner().inner.lock() = clients;
+                }
+                Err(err) => eprintln!("Invalid network settings, using defaults: {}", err),
+            }
+
+            match load_custom_models(app_handle) {
+                Ok(models) => {
+                    let catalog: tauri::State<ModelCatalog> = app.state();
+                    *catalog.inner().custom.lock() = models;
+                }
+                Err(err) => eprintln!("Failed to load custom model catalog: {}", err),
+            }
+
+            // Housekeeping: leftovers are only reported here; `purge_storage` deletes them
+            match scan_models_storage(app_handle, download_state.inner()) {
+                Ok(report) if report.reclaimable_bytes > 0 => println!(
+                    "Models folder has {} stale partial download(s) and {} unrecognized file(s) ({})",
+                    report.stale_temp_files.len(),
+                    report.orphan_files.len(),
+                    format_bytes(report.reclaimable_bytes)
+                ),
+                Ok(_) => {}
+                Err(err) => eprintln!("Failed to scan models folder: {}", err),
+            }
+
             let startup_model_name =
-                load_selected_model(&app_handle).unwrap_or_else(|| DEFAULT_MODEL.to_string());
-            let startup_path = get_model_path_for(&app_handle, &startup_model_name);
-            let startup_exists = startup_path.exists();
+                load_selected_model(app_handle).unwrap_or_else(|| DEFAULT_MODEL.to_string());
+            let startup_path = get_model_path_for(app_handle, &startup_model_name)
+                .map_err(|err| {
+                    eprintln!("Cannot resolve model '{}': {}", startup_model_name, err)
+                })
+                .ok();
+            let startup_exists = startup_path.as_ref().is_some_and(|path| path.exists());
 
             let model_ready = if startup_exists {
                 println!("Whisper model '{}' found, loading...", startup_model_name);
-                match load_whisper_model_for(&app_handle, &startup_model_name) {
-                    Ok(ctx) => {
+                match whisper_state
+                    .inner()
+                    .load_engine_for(app_handle, &startup_model_name)
+                {
+                    Ok(engine) => {
                         {
                             let mut runtime = whisper_state.inner().inner.lock();
-                            runtime.context = Some(ctx);
+                            runtime.engine = Some(engine);
                             runtime.current_model = Some(startup_model_name.clone());
                         }
 
@@ -1258,7 +5908,9 @@ pub fn run() {
                                 .or_insert_with(|| DownloadRecord::new(DownloadStatus::Completed));
                             entry.status = DownloadStatus::Completed;
                             entry.error = None;
-                            if let Ok(meta) = fs::metadata(&startup_path) {
+                            if let Some(meta) =
+                                startup_path.as_ref().and_then(|path| fs::metadata(path).ok())
+                            {
                                 let len = meta.len();
                                 entry.downloaded_bytes = len;
                                 entry.total_bytes = Some(len);
@@ -1266,7 +5918,7 @@ pub fn run() {
                         }
 
                         emit_download_event(
-                            &app_handle,
+                            app_handle,
                             DownloadEventPayload {
                                 model_name: startup_model_name.clone(),
                                 downloaded_bytes: {
@@ -1283,10 +5935,11 @@ pub fn run() {
                                 percent: Some(100.0),
                                 status: "active",
                                 error: None,
+                                ..Default::default()
                             },
                         );
 
-                        persist_selected_model(&app_handle, &startup_model_name);
+                        persist_selected_model(app_handle, &startup_model_name);
 
                         let _ = app_handle.emit(
                             "active-model-changed",
@@ -1310,30 +5963,41 @@ pub fn run() {
                     }
                 }
             } else {
-                println!(
-                    "Whisper model '{}' not found at: {:?}. Starting download...",
-                    startup_model_name, startup_path
-                );
+                if let Some(path) = &startup_path {
+                    println!(
+                        "Whisper model '{}' not found at: {:?}",
+                        startup_model_name, path
+                    );
+                }
 
                 {
                     let mut runtime = whisper_state.inner().inner.lock();
                     runtime.current_model = Some(startup_model_name.clone());
-                    runtime.context = None;
+                    runtime.engine = None;
                 }
 
-                persist_selected_model(&app_handle, &startup_model_name);
+                persist_selected_model(app_handle, &startup_model_name);
 
-                if let Err(err) = spawn_model_download(
-                    &app_handle,
-                    download_state.inner().clone(),
-                    whisper_state.inner().clone(),
-                    startup_model_name.clone(),
-                    false,
+                // Same rules as the watchdog: nothing is fetched when it is off or the user
+                // removed the model
+                match model_watchdog_target(
+                    app_handle,
+                    download_state.inner(),
+                    whisper_state.inner(),
                 ) {
-                    eprintln!(
-                        "Failed to start download for '{}': {}",
-                        startup_model_name, err
-                    );
+                    Some(model_name) => {
+                        println!("Starting download of '{}'...", model_name);
+                        if let Err(err) = spawn_model_download(
+                            app_handle,
+                            download_state.inner().clone(),
+                            whisper_state.inner().clone(),
+                            model_name.clone(),
+                            false,
+                        ) {
+                            eprintln!("Failed to start download for '{}': {}", model_name, err);
+                        }
+                    }
+                    None => println!("Model watchdog policy does not ask for a download"),
                 }
 
                 false
@@ -1387,47 +6051,915 @@ pub fn run() {
                 });
             }
 
-            // Start periodic model check to ensure recommended model is always available
+            let app_handle_for_worker = app_handle.clone();
+            let whisper_state_for_worker = whisper_state.inner().clone();
+            std::thread::spawn(move || {
+                run_transcription_worker(
+                    app_handle_for_worker,
+                    whisper_state_for_worker,
+                    transcription_queue,
+                    transcription_receiver,
+                );
+            });
+
+            let app_handle_for_devices = app_handle.clone();
+            std::thread::spawn(move || run_input_device_monitor(app_handle_for_devices));
+
+            // Keep the model required by the watchdog policy downloaded
             let app_handle_for_check = app_handle.clone();
             let download_state_for_check = download_state.inner().clone();
             let whisper_state_for_check = whisper_state.inner().clone();
             std::thread::spawn(move || {
-                loop {
-                    std::thread::sleep(std::time::Duration::from_secs(30));
+                run_model_watchdog(
+                    app_handle_for_check,
+                    download_state_for_check,
+                    whisper_state_for_check,
+                    watchdog_receiver,
+                );
+            });
 
-                    let default_path = get_model_path_for(&app_handle_for_check, DEFAULT_MODEL);
+            Ok(())
+        })
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
 
-                    // Check if default model exists
-                    if !default_path.exists() {
-                        println!("Recommended model missing, starting automatic download...");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
 
-                        // Check if not already downloading
-                        let already_downloading = {
-                            let map = download_state_for_check.inner.lock();
-                            map.get(DEFAULT_MODEL)
-                                .map(|entry| entry.status == DownloadStatus::Downloading)
-                                .unwrap_or(false)
-                        };
+    fn sine(rate: u32, frequency: f32, seconds: f32) -> Vec<f32> {
+        let len = (rate as f32 * seconds) as usize;
+        (0..len)
+            .map(|i| (2.0 * PI * frequency * i as f32 / rate as f32).sin())
+            .collect()
+    }
 
-                        if !already_downloading {
-                            if let Err(err) = spawn_model_download(
-                                &app_handle_for_check,
-                                download_state_for_check.clone(),
-                                whisper_state_for_check.clone(),
-                                DEFAULT_MODEL.to_string(),
-                                false,
-                            ) {
-                                eprintln!("Failed to auto-download missing model: {}", err);
-                            } else {
-                                println!("Automatic download of recommended model started");
-                            }
-                        }
+    // Logarithmic sweep across the band Whisper cares about
+    fn sweep(rate: u32, from: f32, to: f32, seconds: f32) -> Vec<f32> {
+        let len = (rate as f32 * seconds) as usize;
+        let k = (to / from).ln() / seconds;
+        (0..len)
+            .map(|i| {
+                let t = i as f32 / rate as f32;
+                (2.0 * PI * from * ((k * t).exp() - 1.0) / k).sin()
+            })
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    // Feed the input in callback-sized pieces like cpal does, then flush
+    fn resample(rate: u32, input: &[f32]) -> Vec<f32> {
+        let mut resampler = StreamResampler::new(rate).unwrap();
+        let mut output = Vec::new();
+        for chunk in input.chunks(480) {
+            resampler.push(chunk.iter().copied(), &mut output);
+        }
+        resampler.flush(&mut output);
+        output
+    }
+
+    const DEVICE_RATES: [u32; 4] = [22050, 44100, 48000, 96000];
+
+    #[test]
+    fn resampled_sweep_has_expected_length() {
+        for rate in DEVICE_RATES {
+            let input = sweep(rate, 100.0, 7000.0, 2.3);
+            let output = resample(rate, &input);
+            let expected = input.len() * 16000 / rate as usize;
+            assert!(
+                output.len().abs_diff(expected) <= 1,
+                "{} Hz: {} samples, expected {}",
+                rate,
+                output.len(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn resampling_preserves_tone_frequency_and_phase() {
+        for rate in DEVICE_RATES {
+            let output = resample(rate, &sine(rate, 1000.0, 1.0));
+            let expected = sine(16000, 1000.0, 1.0);
+            // Skip the edges, where the filter has only part of the signal
+            let middle = 1600..output.len() - 1600;
+            let max_error = middle
+                .clone()
+                .map(|i| (output[i] - expected[i]).abs())
+                .fold(0.0, f32::max);
+            assert!(max_error < 0.05, "{} Hz: max error {}", rate, max_error);
+
+            let crossings = output[middle.clone()]
+                .windows(2)
+                .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+                .count();
+            let frequency = crossings as f32 / 2.0 / (middle.len() as f32 / 16000.0);
+            assert!(
+                (frequency - 1000.0).abs() < 10.0,
+                "{} Hz: measured {} Hz",
+                rate,
+                frequency
+            );
+        }
+    }
+
+    #[test]
+    fn resampling_removes_content_above_8khz() {
+        for rate in DEVICE_RATES {
+            // Stay below the device's own Nyquist frequency
+            let frequency = if rate > 24000 { 12000.0 } else { 10000.0 };
+            let output = resample(rate, &sine(rate, frequency, 1.0));
+            let level = rms(&output);
+            assert!(
+                level < 0.01,
+                "{} Hz: {} Hz tone left at {}",
+                rate,
+                frequency,
+                level
+            );
+        }
+    }
+
+    #[test]
+    fn flush_drains_filter_delay() {
+        for rate in DEVICE_RATES {
+            let input = sweep(rate, 100.0, 7000.0, 1.0);
+            let expected = input.len() * 16000 / rate as usize;
+
+            let mut resampler = StreamResampler::new(rate).unwrap();
+            let mut output = Vec::new();
+            resampler.push(input.iter().copied(), &mut output);
+            assert!(output.len() < expected, "{} Hz: nothing held back", rate);
+
+            resampler.flush(&mut output);
+            assert!(
+                output.len().abs_diff(expected) <= 1,
+                "{} Hz: {} samples, expected {}",
+                rate,
+                output.len(),
+                expected
+            );
+            // The end of the sweep comes out instead of silence
+            assert!(rms(&output[output.len() - 160..]) > 0.3, "{} Hz", rate);
+        }
+    }
+
+    #[test]
+    fn audio_at_16khz_passes_through() {
+        let input = sweep(16000, 100.0, 7000.0, 0.5);
+        assert_eq!(resample(16000, &input), input);
+    }
+
+    #[test]
+    fn stereo_is_downmixed_by_averaging() {
+        let mix = ChannelMix::new(2, None);
+        assert_eq!(mix, ChannelMix::Downmix(2));
+        let interleaved = [0.5f32, -0.5, 1.0, 0.0, -0.25, -0.75];
+        let mono: Vec<f32> = mix.frames(&interleaved).collect();
+        assert_eq!(mono, vec![0.0, 0.5, -0.5]);
+    }
+
+    #[test]
+    fn selected_channel_is_used_alone() {
+        let mix = ChannelMix::new(4, Some(2));
+        assert_eq!(
+            mix,
+            ChannelMix::Select {
+                channel: 2,
+                channels: 4
+            }
+        );
+        let interleaved: Vec<i16> = vec![1, 2, i16::MAX, 4, 5, 6, i16::MIN, 8];
+        let mono: Vec<f32> = mix.frames(&interleaved).collect();
+        assert_eq!(mono.len(), 2);
+        assert!((mono[0] - 1.0).abs() < 1e-4);
+        assert_eq!(mono[1], -1.0);
+    }
+
+    #[test]
+    fn missing_channel_falls_back_to_downmix() {
+        let mix = ChannelMix::new(2, Some(5));
+        assert_eq!(mix, ChannelMix::Downmix(2));
+        let mono: Vec<f32> = mix.frames(&[0.2f32, 0.4]).collect();
+        assert!((mono[0] - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn mono_input_is_unchanged() {
+        let samples = [0.1f32, -0.2, 0.3];
+        for mix in [ChannelMix::new(1, None), ChannelMix::new(1, Some(0))] {
+            assert_eq!(mix.frames(&samples).collect::<Vec<_>>(), samples.to_vec());
+        }
+    }
+
+    #[test]
+    fn incomplete_trailing_frame_is_ignored() {
+        let mono: Vec<f32> = ChannelMix::new(2, None)
+            .frames(&[0.5f32, 0.5, 1.0])
+            .collect();
+        assert_eq!(mono, vec![0.5]);
+    }
+
+    // Quiet background hiss, reproducible between runs
+    fn noise(len: usize, level: f32) -> Vec<f32> {
+        let mut state = 0x1234_5678u32;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                ((state >> 8) as f32 / (1 << 24) as f32 - 0.5) * 2.0 * level
+            })
+            .collect()
+    }
+
+    fn add(clip: &mut [f32], at: usize, signal: &[f32]) {
+        for (sample, value) in clip[at..].iter_mut().zip(signal) {
+            *sample += value;
+        }
+    }
+
+    #[test]
+    fn silence_has_no_speech() {
+        let settings = VadSettings::default();
+        assert!(detect_speech(&[], &settings).is_none());
+        assert!(detect_speech(&vec![0.0; 32000], &settings).is_none());
+        assert!(detect_speech(&noise(48000, 0.003), &settings).is_none());
+    }
+
+    #[test]
+    fn speech_filling_the_clip_is_kept() {
+        // Quiet dictation with no pause before, after or in between
+        let settings = VadSettings::default();
+        let voice: Vec<f32> = sine(16000, 220.0, 4.0)
+            .iter()
+            .zip(sine(16000, 660.0, 4.0))
+            .map(|(low, high)| 0.02 * low + 0.01 * high)
+            .collect();
+        let mut clip = noise(voice.len(), 0.003);
+        add(&mut clip, 0, &voice);
+
+        let span = detect_speech(&clip, &settings).expect("speech expected");
+        assert_eq!(span.range, 0..clip.len());
+        assert!(
+            span.speech_samples >= clip.len() - 2 * VAD_FRAME_SAMPLES,
+            "{:?}",
+            span
+        );
+    }
+
+    #[test]
+    fn tone_burst_in_noise_is_trimmed_with_padding() {
+        let settings = VadSettings::default();
+        // 1s of noise, 1.5s of a voice-like 220Hz tone, 1s of noise
+        let mut clip = noise(56000, 0.003);
+        let burst: Vec<f32> = sine(16000, 220.0, 1.5).iter().map(|s| s * 0.3).collect();
+        add(&mut clip, 16000, &burst);
+
+        let span = detect_speech(&clip, &settings).expect("speech expected");
+        let padding = ms_to_samples(settings.padding_ms);
+        let frame = VAD_FRAME_SAMPLES;
+        assert!(
+            span.range.start.abs_diff(16000 - padding) <= frame,
+            "{:?}",
+            span
+        );
+        assert!(
+            span.range.end.abs_diff(40000 + padding) <= frame,
+            "{:?}",
+            span
+        );
+        assert!(
+            span.speech_samples.abs_diff(24000) <= 2 * frame,
+            "{:?}",
+            span
+        );
+    }
+
+    #[test]
+    fn short_click_is_not_speech() {
+        let settings = VadSettings::default();
+        let mut clip = noise(32000, 0.003);
+        // A 40ms key click, well under `min_speech_ms`
+        let click: Vec<f32> = sine(16000, 1000.0, 0.04).iter().map(|s| s * 0.8).collect();
+        add(&mut clip, 12000, &click);
+        assert!(detect_speech(&clip, &settings).is_none());
+
+        // The same click next to real speech does not stretch the span
+        let burst: Vec<f32> = sine(16000, 220.0, 1.0).iter().map(|s| s * 0.3).collect();
+        let mut clip = noise(64000, 0.003);
+        add(&mut clip, 4000, &click);
+        add(&mut clip, 32000, &burst);
+        let span = detect_speech(&clip, &settings).expect("speech expected");
+        assert!(span.range.start > 16000, "{:?}", span);
+    }
+
+    // Minimal stand-in for a model host. `respond` gets the index of the connection and the
+    // lowercased request head; every head is also kept in `requests`.
+    struct TestServer {
+        url: String,
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    fn serve_with(
+        respond: impl Fn(usize, &str, &mut std::net::TcpStream) + Send + 'static,
+    ) -> TestServer {
+        use std::io::{BufRead, BufReader};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/model.bin", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+        std::thread::spawn(move || {
+            for (index, stream) in listener.incoming().enumerate() {
+                let mut stream = stream.unwrap();
+                let mut head = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                        break;
                     }
+                    head.push_str(&line.to_ascii_lowercase());
                 }
-            });
+                log.lock().push(head.clone());
+                respond(index, &head, &mut stream);
+            }
+        });
+        TestServer { url, requests }
+    }
 
-            Ok(())
+    // Serves `body` with an ETag, honours `Range` when `If-Range` matches, and can cut the
+    // first connection off after `drop_after` bytes
+    fn serve(body: Vec<u8>, etag: &'static str, drop_after: Option<usize>) -> TestServer {
+        serve_with(move |index, head, stream| {
+            let header = |name: &str| {
+                head.lines()
+                    .find_map(|line| line.strip_prefix(name))
+                    .map(|value| value.trim().to_string())
+            };
+            let range_start = header("range: bytes=")
+                .and_then(|range| range.trim_end_matches('-').parse::<usize>().ok())
+                .filter(|_| header("if-range:").is_none_or(|value| value == etag));
+
+            let len = body.len();
+            let (status, extra, part) = match range_start {
+                Some(start) if start >= len => (
+                    "416 Range Not Satisfiable",
+                    format!("Content-Range: bytes */{}\r\n", len),
+                    &body[..0],
+                ),
+                Some(start) => (
+                    "206 Partial Content",
+                    format!("Content-Range: bytes {}-{}/{}\r\n", start, len - 1, len),
+                    &body[start..],
+                ),
+                None => ("200 OK", String::new(), &body[..]),
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {}\r\nETag: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+                status,
+                etag,
+                part.len(),
+                extra
+            );
+            let sent = match drop_after {
+                Some(limit) if index == 0 => &part[..limit.min(part.len())],
+                _ => part,
+            };
+            let _ = stream.write_all(sent);
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+    }
+
+    // Answers every request with `status` and no body, plus any `extra` headers
+    fn serve_status(status: &'static str, extra: String) -> TestServer {
+        serve_with(move |_, _, stream| {
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {}\r\n{}Content-Length: 0\r\nConnection: close\r\n\r\n",
+                status, extra
+            );
+        })
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sotto-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn model_bytes(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    fn fetch(server: &TestServer, temp_path: &Path) -> (Result<(), String>, Vec<u64>) {
+        let source = DownloadSource {
+            url: server.url.clone(),
+            auth_header: None,
+        };
+        let mut offsets = Vec::new();
+        let client = HttpClientSet::build(&NetworkSettings::default())
+            .unwrap()
+            .client;
+        let result = fetch_to_partial_file(
+            &client,
+            &source,
+            temp_path,
+            &AtomicBool::new(false),
+            |progress| {
+                if let TransferProgress::Started { resume_offset, .. } = progress {
+                    offsets.push(resume_offset);
+                }
+            },
+        );
+        (result.map_err(|err| err.to_string()), offsets)
+    }
+
+    #[test]
+    fn dropped_download_resumes_from_partial_file() {
+        let body = model_bytes(300_000);
+        let server = serve(body.clone(), "\"v1\"", Some(120_000));
+        let dir = test_dir("resume");
+        let temp_path = dir.join("ggml-test.download");
+
+        let (result, offsets) = fetch(&server, &temp_path);
+        assert!(result.is_err(), "the first connection should be cut off");
+        assert_eq!(offsets, [0]);
+        let partial = std::fs::metadata(&temp_path).unwrap().len();
+        assert!(partial > 0 && partial < body.len() as u64);
+
+        let (result, offsets) = fetch(&server, &temp_path);
+        assert_eq!(result, Ok(()));
+        assert_eq!(offsets, [partial]);
+        assert!(std::fs::read(&temp_path).unwrap() == body);
+
+        let requests = server.requests.lock();
+        assert!(requests[1].contains(&format!("range: bytes={}-", partial)));
+        assert!(requests[1].contains("if-range: \"v1\""));
+        drop(requests);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn changed_remote_file_restarts_download() {
+        let dir = test_dir("changed");
+        let temp_path = dir.join("ggml-test.download");
+        std::fs::write(&temp_path, model_bytes(50_000)).unwrap();
+        std::fs::write(get_validator_path_for(&temp_path), "\"v1\"").unwrap();
+
+        let body: Vec<u8> = model_bytes(200_000).iter().map(|b| b ^ 0xff).collect();
+        let server = serve(body.clone(), "\"v2\"", None);
+        let (result, offsets) = fetch(&server, &temp_path);
+        assert_eq!(result, Ok(()));
+        assert_eq!(offsets, [0]);
+        assert!(std::fs::read(&temp_path).unwrap() == body);
+        assert_eq!(
+            std::fs::read_to_string(get_validator_path_for(&temp_path)).unwrap(),
+            "\"v2\""
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn complete_partial_file_is_not_downloaded_again() {
+        let body = model_bytes(80_000);
+        let server = serve(body.clone(), "\"v1\"", None);
+        let dir = test_dir("complete");
+        let temp_path = dir.join("ggml-test.download");
+        std::fs::write(&temp_path, &body).unwrap();
+        std::fs::write(get_validator_path_for(&temp_path), "\"v1\"").unwrap();
+
+        let (result, offsets) = fetch(&server, &temp_path);
+        assert_eq!(result, Ok(()));
+        assert!(offsets.is_empty());
+        assert!(std::fs::read(&temp_path).unwrap() == body);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn download_with_wrong_checksum_is_not_installed() {
+        let dir = test_dir("checksum");
+        let expected_path = dir.join("expected.bin");
+        std::fs::write(&expected_path, model_bytes(60_000)).unwrap();
+        let expected = compute_sha256(&expected_path).unwrap();
+
+        // A mirror serving different bytes of the same length
+        let body: Vec<u8> = model_bytes(60_000).iter().map(|b| b ^ 0x55).collect();
+        let server = serve(body, "\"v1\"", None);
+        let temp_path = dir.join("ggml-test.download");
+        let model_path = dir.join("ggml-test.bin");
+        let (result, _) = fetch(&server, &temp_path);
+        assert_eq!(result, Ok(()));
+
+        let err = install_downloaded_file(
+            &temp_path,
+            &model_path,
+            Some(&expected),
+            false,
+            &AtomicBool::new(false),
+        )
+        .unwrap_err();
+        assert_eq!(
+            DownloadStatus::for_error(err.as_ref()),
+            DownloadStatus::VerificationFailed
+        );
+        assert!(!model_path.exists());
+        assert!(!temp_path.exists());
+        assert!(!get_validator_path_for(&temp_path).exists());
+
+        // The right bytes are moved into place
+        let server = serve(model_bytes(60_000), "\"v1\"", None);
+        let (result, _) = fetch(&server, &temp_path);
+        assert_eq!(result, Ok(()));
+        install_downloaded_file(
+            &temp_path,
+            &model_path,
+            Some(&expected),
+            false,
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+        assert_eq!(compute_sha256(&model_path).unwrap(), expected);
+        assert!(!temp_path.exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    fn mirror_source(url: &str, token: Option<&str>) -> DownloadSource {
+        DownloadSource {
+            url: url.to_string(),
+            auth_header: token.map(|token| ("X-Mirror-Token".to_string(), token.to_string())),
+        }
+    }
+
+    #[test]
+    fn mirrors_rewrite_catalog_urls() {
+        let mut config = AppConfig {
+            download_mirrors: vec![
+                DownloadMirror {
+                    base_url: "https://mirror.example/whisper/".to_string(),
+                    auth_header_name: Some("X-Mirror-Token".to_string()),
+                    auth_header_value: Some("secret".to_string()),
+                },
+                DownloadMirror {
+                    base_url: "http://10.0.0.2/models".to_string(),
+                    auth_header_name: None,
+                    auth_header_value: None,
+                },
+            ],
+            ..Default::default()
+        };
+        let upstream = format!("{}/ggml-base.en.bin", HUGGINGFACE_MODELS_URL);
+
+        let sources = resolve_download_sources(&config, &upstream);
+        let urls: Vec<&str> = sources.iter().map(|source| source.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://mirror.example/whisper/ggml-base.en.bin",
+                "http://10.0.0.2/models/ggml-base.en.bin",
+                upstream.as_str(),
+            ]
+        );
+        assert_eq!(
+            sources[0].auth_header,
+            Some(("X-Mirror-Token".to_string(), "secret".to_string()))
+        );
+        assert!(sources[1].auth_header.is_none() && sources[2].auth_header.is_none());
+
+        config.disable_upstream_fallback = true;
+        assert_eq!(resolve_download_sources(&config, &upstream).len(), 2);
+
+        // Custom models keep their own URL and never get a mirror's credentials
+        let sources = resolve_download_sources(&config, "https://example.com/ggml-custom.bin");
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].url, "https://example.com/ggml-custom.bin");
+        assert!(sources[0].auth_header.is_none());
+    }
+
+    #[test]
+    fn failed_mirror_falls_back_to_next_source() {
+        let body = model_bytes(150_000);
+        let broken = serve_status("404 Not Found", String::new());
+        // Drops the first connection, so the fallback is also retried once
+        let working = serve(body.clone(), "\"v1\"", Some(50_000));
+        let sources = [
+            mirror_source(&broken.url, Some("secret")),
+            mirror_source(&working.url, None),
+        ];
+        let settings = NetworkSettings {
+            max_retries: 1,
+            retry_backoff_ms: 0,
+            ..Default::default()
+        };
+        let client = HttpClientSet::build(&settings).unwrap().client;
+        let dir = test_dir("fallback");
+        let temp_path = dir.join("ggml-test.download");
+        let cancel = AtomicBool::new(false);
+
+        let result = download_from_sources(&settings, "test", &sources, &cancel, |source| {
+            fetch_to_partial_file(&client, source, &temp_path, &cancel, |_| {})
+        });
+        assert!(
+            result.is_ok(),
+            "{:?}",
+            result.map_err(|err| err.to_string())
+        );
+        assert!(std::fs::read(&temp_path).unwrap() == body);
+
+        // A 404 is final, so the broken mirror is not retried
+        let broken_requests = broken.requests.lock();
+        assert_eq!(broken_requests.len(), 1);
+        assert!(broken_requests[0].contains("x-mirror-token: secret"));
+        let working_requests = working.requests.lock();
+        assert_eq!(working_requests.len(), 2);
+        assert!(working_requests[1].contains("range: bytes="));
+        assert!(!working_requests[0].contains("x-mirror-token"));
+        drop((broken_requests, working_requests));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn mirror_auth_header_is_not_sent_to_redirect_target() {
+        let body = model_bytes(40_000);
+        let target = serve(body.clone(), "\"v1\"", None);
+        let mirror = serve_status("302 Found", format!("Location: {}\r\n", target.url));
+        let dir = test_dir("redirect");
+        let temp_path = dir.join("ggml-test.download");
+
+        let source = mirror_source(&mirror.url, Some("secret"));
+        let client = HttpClientSet::build(&NetworkSettings::default())
+            .unwrap()
+            .client;
+        let result = fetch_to_partial_file(
+            &client,
+            &source,
+            &temp_path,
+            &AtomicBool::new(false),
+            |_| {},
+        );
+        assert!(result.is_ok());
+        assert!(std::fs::read(&temp_path).unwrap() == body);
+        assert!(mirror.requests.lock()[0].contains("x-mirror-token: secret"));
+        assert!(!target.requests.lock()[0].contains("x-mirror-token"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn repeated_overlap_words_are_stripped() {
+        let committed = " The quick brown fox";
+        assert_eq!(
+            strip_repeated_words(committed, " brown fox jumps over"),
+            " jumps over"
+        );
+        // Only the last committed word came around again
+        assert_eq!(strip_repeated_words(committed, " fox jumps"), " jumps");
+        assert_eq!(
+            strip_repeated_words(committed, " jumps over"),
+            " jumps over"
+        );
+        // Case and punctuation differ between passes
+        assert_eq!(
+            strip_repeated_words(" Hello, World.", " world! And more"),
+            " And more"
+        );
+        assert_eq!(strip_repeated_words(committed, " Brown fox."), "");
+        assert_eq!(strip_repeated_words("", " brown fox"), " brown fox");
+        // The overlap is too short to repeat more than a few words
+        assert_eq!(
+            strip_repeated_words(" one two three four five", " one two three four five six"),
+            " one two three four five six"
+        );
+    }
+
+    fn segment(text: &str, start_ms: i64, end_ms: i64) -> TranscriptSegment {
+        TranscriptSegment {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            avg_token_probability: None,
+        }
+    }
+
+    #[test]
+    fn streaming_passes_commit_stable_segments() {
+        let mut progress = StreamingProgress::default();
+
+        // Short windows stay tentative
+        let tentative = progress.apply_pass(0, 5 * 16000, vec![segment(" Hello there", 0, 4000)]);
+        assert_eq!(tentative, " Hello there");
+        assert_eq!(progress.committed_text, "");
+        assert_eq!(progress.window_start(), 0);
+
+        // Past `STREAM_WINDOW_SAMPLES` everything but the last segment is committed
+        let tentative = progress.apply_pass(
+            0,
+            STREAM_WINDOW_SAMPLES + 8000,
+            vec![
+                segment(" Hello there,", 0, 4000),
+                segment(" general Kenobi.", 4000, 9000),
+                segment(" You are", 9000, 10400),
+            ],
+        );
+        assert_eq!(tentative, " You are");
+        assert_eq!(progress.committed_text, " Hello there, general Kenobi.");
+        assert_eq!(progress.committed_samples, 9000 * 16);
+        assert_eq!(progress.window_start(), 9000 * 16 - STREAM_OVERLAP_SAMPLES);
+
+        // The next window decodes the overlap again; the repeated word is not committed twice
+        let window_start = progress.window_start();
+        let tentative = progress.apply_pass(
+            window_start,
+            3 * 16000,
+            vec![segment(" Kenobi. You are a bold one.", 0, 3000)],
+        );
+        assert_eq!(tentative, " You are a bold one.");
+
+        // At `STREAM_MAX_WINDOW_SAMPLES` the whole window is committed
+        let tentative = progress.apply_pass(
+            window_start,
+            STREAM_MAX_WINDOW_SAMPLES,
+            vec![segment(" Kenobi. You are a bold one.", 0, 25000)],
+        );
+        assert_eq!(tentative, "");
+        assert_eq!(
+            progress.committed_text,
+            " Hello there, general Kenobi. You are a bold one."
+        );
+        assert_eq!(
+            progress.committed_samples,
+            window_start + STREAM_MAX_WINDOW_SAMPLES
+        );
+    }
+
+    #[test]
+    fn streaming_prompt_carries_the_end_of_committed_text() {
+        let mut progress = StreamingProgress {
+            base_prompt: Some("Glossary: Kenobi.".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(progress.prompt().as_deref(), Some("Glossary: Kenobi."));
+
+        progress.committed_text = format!(" {}", "word ".repeat(100));
+        let prompt = progress.prompt().unwrap();
+        let tail = prompt.strip_prefix("Glossary: Kenobi. ").unwrap();
+        assert_eq!(tail.chars().count(), STREAM_PROMPT_CHARS);
+        assert!(progress.committed_text.trim().ends_with(tail));
+    }
+
+    // Stands in for whisper.cpp: the "model" file holds the transcript the engine returns
+    struct MockEngine {
+        transcript: String,
+    }
+
+    impl TranscriptionEngine for MockEngine {
+        fn load(model_path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+            let transcript = fs::read_to_string(model_path)?;
+            Ok(Self { transcript })
+        }
+
+        fn capabilities(&self) -> EngineCapabilities {
+            EngineCapabilities {
+                multilingual: false,
+                translation: false,
+                language_detection: false,
+                sample_rate: 16000,
+            }
+        }
+
+        fn transcribe(
+            &mut self,
+            samples: &[f32],
+            options: &TranscriptionOptions,
+        ) -> Result<TranscriptionResult, Box<dyn std::error::Error>> {
+            let end_ms = (samples.len() / 16) as i64;
+            let text = match &options.initial_prompt {
+                Some(prompt) => format!(" {} {} ", prompt, self.transcript),
+                None => format!(" {} ", self.transcript),
+            };
+            Ok(TranscriptionResult {
+                segments: vec![segment(&text, 0, end_ms)],
+                text,
+                language: Some("en".to_string()),
+                ..Default::default()
+            })
+        }
+    }
+
+    fn mock_manager() -> WhisperManager {
+        WhisperManager {
+            load_engine: Arc::new(|path| Ok(Box::new(MockEngine::load(path)?))),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn transcription_runs_through_the_loaded_engine() {
+        let dir = std::env::temp_dir().join(format!("mock-engine-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let model_path = dir.join("ggml-mock.bin");
+        fs::write(&model_path, "hello there").unwrap();
+
+        let manager = mock_manager();
+        let audio = vec![0.1; 16000];
+        let options = TranscriptionOptions::default();
+        assert!(manager
+            .transcribe_with(&audio, &options)
+            .unwrap_err()
+            .is::<EngineNotLoaded>());
+        assert!(manager.load_engine_at(&dir.join("missing.bin")).is_err());
+
+        let engine = manager.load_engine_at(&model_path).unwrap();
+        manager.inner.lock().engine = Some(engine);
+
+        let result = manager.transcribe_with(&audio, &options).unwrap();
+        assert_eq!(result.text, "hello there");
+        assert_eq!(result.language.as_deref(), Some("en"));
+        assert_eq!(result.segments.len(), 1);
+        assert_eq!(result.segments[0].end_ms, 1000);
+
+        let options = TranscriptionOptions {
+            initial_prompt: Some("Kenobi.".to_string()),
+            ..Default::default()
+        };
+        let result = manager.transcribe_with(&audio, &options).unwrap();
+        assert_eq!(result.text, "Kenobi. hello there");
+
+        // Under 0.3s the engine is not called at all
+        let result = manager.transcribe_with(&audio[..4000], &options).unwrap();
+        assert!(result.text.is_empty());
+        assert!(result.segments.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn progress_emits_are_throttled() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut throttle = ProgressThrottle::new(start, 0);
+
+        assert!(throttle.should_emit(at(0), Some(0.0)));
+        // Too soon and too little progress
+        assert!(!throttle.should_emit(at(100), Some(0.5)));
+        // Enough progress
+        assert!(throttle.should_emit(at(150), Some(1.5)));
+        assert!(!throttle.should_emit(at(200), Some(2.0)));
+        // Enough time
+        assert!(throttle.should_emit(at(400), Some(2.0)));
+
+        // Unknown total: only time counts
+        let mut throttle = ProgressThrottle::new(start, 0);
+        assert!(throttle.should_emit(at(0), None));
+        assert!(!throttle.should_emit(at(249), None));
+        assert!(throttle.should_emit(at(250), None));
+    }
+
+    #[test]
+    fn final_progress_is_always_emitted() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut throttle = ProgressThrottle::new(start, 0);
+
+        assert!(throttle.should_emit(at(0), Some(99.5)));
+        assert!(throttle.should_emit(at(10), Some(100.0)));
+        assert!(!throttle.should_emit(at(20), Some(100.0)));
+    }
+
+    #[test]
+    fn download_speed_averages_over_the_window() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut throttle = ProgressThrottle::new(start, 0);
+        assert_eq!(throttle.bytes_per_second(), None);
+
+        throttle.record(at(1), 1000);
+        throttle.record(at(2), 2000);
+        assert_eq!(throttle.bytes_per_second(), Some(1000.0));
+
+        // Samples older than the window stop counting once a newer one covers it
+        for secs in 3..=10 {
+            throttle.record(at(secs), 2000 + (secs - 2) * 3000);
+        }
+        assert_eq!(throttle.bytes_per_second(), Some(3000.0));
+    }
+
+    #[test]
+    fn eta_needs_a_total_and_a_speed() {
+        assert_eq!(
+            estimate_eta_seconds(500, Some(1500), Some(250.0)),
+            Some(4.0)
+        );
+        assert_eq!(
+            estimate_eta_seconds(1500, Some(1500), Some(250.0)),
+            Some(0.0)
+        );
+        assert_eq!(estimate_eta_seconds(500, None, Some(250.0)), None);
+        assert_eq!(estimate_eta_seconds(500, Some(1500), Some(0.0)), None);
+        assert_eq!(estimate_eta_seconds(500, Some(1500), None), None);
+    }
 }
@@ -57,16 +57,20 @@ window.addEventListener("DOMContentLoaded", () => {
   // Handle model selection cards
   const modelCards = Array.from(document.querySelectorAll<HTMLElement>('.model-card'));
   if (modelCards.length > 0) {
-    type ModelAction = 'use' | 'download' | 'refresh' | 'remove' | 'confirm-remove' | 'cancel-remove';
+    type ModelAction = 'use' | 'download' | 'refresh' | 'cancel' | 'remove' | 'confirm-remove' | 'cancel-remove';
 
     interface BackendModelStatus {
       name: string;
       size_mb: number;
       is_downloaded: boolean;
       is_downloading: boolean;
+      is_queued: boolean;
+      is_paused: boolean;
+      queue_position: number | null;
       is_active: boolean;
       downloaded_bytes: number;
       total_bytes: number | null;
+      resume_offset: number | null;
       error: string | null;
     }
 
@@ -90,10 +94,17 @@ window.addEventListener("DOMContentLoaded", () => {
     interface ModelState {
       isDownloaded: boolean;
       isDownloading: boolean;
+      isVerifying: boolean;
+      isQueued: boolean;
+      isPaused: boolean;
+      queuePosition: number | null;
       isActive: boolean;
       downloadedBytes: number;
       totalBytes: number | null;
       progressPercent: number | null;
+      resumeOffset: number | null;
+      bytesPerSecond: number | null;
+      etaSeconds: number | null;
       error?: string;
       inFlightAction: ModelAction | null;
       pendingLabel: string | null;
@@ -107,6 +118,9 @@ window.addEventListener("DOMContentLoaded", () => {
       percent?: number | null;
       status: string;
       error?: string | null;
+      resumeOffset?: number | null;
+      bytesPerSecond?: number | null;
+      etaSeconds?: number | null;
     }
 
     interface ActiveModelPayload {
@@ -117,6 +131,7 @@ window.addEventListener("DOMContentLoaded", () => {
       use: 'Use',
       download: 'Download',
       refresh: 'Refresh',
+      cancel: 'Cancel',
       remove: 'Remove',
       'confirm-remove': 'Remove',
       'cancel-remove': 'Cancel',
@@ -149,10 +164,17 @@ window.addEventListener("DOMContentLoaded", () => {
           modelStates.set(modelName, {
             isDownloaded: false,
             isDownloading: false,
+            isVerifying: false,
+            isQueued: false,
+            isPaused: false,
+            queuePosition: null,
             isActive: false,
             downloadedBytes: 0,
             totalBytes: null,
             progressPercent: null,
+            resumeOffset: null,
+            bytesPerSecond: null,
+            etaSeconds: null,
             error: undefined,
             inFlightAction: null,
             pendingLabel: null,
@@ -185,6 +207,17 @@ window.addEventListener("DOMContentLoaded", () => {
       return `${bytes} B`;
     };
 
+    const formatDuration = (seconds: number): string => {
+      const rounded = Math.max(0, Math.round(seconds));
+      if (rounded >= 3600) {
+        return `${Math.floor(rounded / 3600)}h ${Math.floor((rounded % 3600) / 60)}m`;
+      }
+      if (rounded >= 60) {
+        return `${Math.floor(rounded / 60)}m ${rounded % 60}s`;
+      }
+      return `${rounded}s`;
+    };
+
     const extractErrorMessage = (error: unknown): string => {
       if (error instanceof Error) return error.message;
       if (typeof error === 'string') return error;
@@ -211,9 +244,20 @@ window.addEventListener("DOMContentLoaded", () => {
         if (state.isConfirmingRemoval) {
           return 'Confirm removal?';
         }
+        if (state.isVerifying) {
+          return 'Verifying…';
+        }
+        if (state.isPaused) {
+          return 'Paused in queue';
+        }
+        if (state.isQueued) {
+          return state.queuePosition !== null ? `Queued (#${state.queuePosition + 1})` : 'Queued';
+        }
         if (state.isDownloading) {
+          const rate = state.bytesPerSecond ? ` · ${formatBytes(state.bytesPerSecond)}/s` : '';
+          const eta = state.etaSeconds !== null ? ` · ${formatDuration(state.etaSeconds)} left` : '';
           if (state.progressPercent !== null) {
-            return `Downloading ${Math.round(state.progressPercent)}%`;
+            return `Downloading ${Math.round(state.progressPercent)}%${rate}${eta}`;
           }
           if (state.downloadedBytes > 0) {
             return `Downloading ${formatBytes(state.downloadedBytes)}`;
@@ -223,6 +267,9 @@ window.addEventListener("DOMContentLoaded", () => {
         if (state.error) {
           return `Error: ${truncate(state.error)}`;
         }
+        if (state.resumeOffset && !state.isDownloaded) {
+          return `Partially downloaded (${formatBytes(state.resumeOffset)})`;
+        }
         if (state.isActive && !state.isDownloaded) {
           return 'Active · Download required';
         }
@@ -262,7 +309,12 @@ window.addEventListener("DOMContentLoaded", () => {
 
         elements.buttons.use.hidden = !(state.isDownloaded && !state.isActive && !state.isDownloading);
         elements.buttons.download.hidden = state.isDownloaded || state.isDownloading;
+        elements.buttons.download.textContent = state.resumeOffset && !state.isDownloading
+          ? 'Resume'
+          : ACTION_LABELS.download;
         elements.buttons.refresh.hidden = !state.isDownloaded || state.isDownloading;
+        elements.buttons.cancel.hidden = !state.isDownloading || state.isVerifying;
+        elements.buttons.cancel.disabled = state.inFlightAction !== null;
         elements.buttons.remove.hidden = !state.isDownloaded || state.isActive || state.isDownloading;
 
         elements.statusLabel.textContent = formatStatus(state);
@@ -324,6 +376,7 @@ window.addEventListener("DOMContentLoaded", () => {
 
         const downloadButton = createActionButton('download');
         const refreshButton = createActionButton('refresh');
+        const cancelButton = createActionButton('cancel');
         const removeButton = createActionButton('remove');
 
         const metaRow = document.createElement('div');
@@ -377,6 +430,7 @@ window.addEventListener("DOMContentLoaded", () => {
             use: primaryButton,
             download: downloadButton,
             refresh: refreshButton,
+            cancel: cancelButton,
             remove: removeButton,
             'confirm-remove': confirmYes,
             'cancel-remove': confirmNo,
@@ -414,9 +468,13 @@ window.addEventListener("DOMContentLoaded", () => {
             const state = ensureState(status.name);
             state.isDownloaded = status.is_downloaded;
             state.isDownloading = status.is_downloading;
+            state.isQueued = status.is_queued;
+            state.isPaused = status.is_paused;
+            state.queuePosition = status.queue_position ?? null;
             state.isActive = status.is_active;
             state.downloadedBytes = status.downloaded_bytes ?? 0;
             state.totalBytes = status.total_bytes ?? null;
+            state.resumeOffset = status.resume_offset ?? null;
             state.progressPercent = status.is_downloading
               ? computePercent(state.downloadedBytes, state.totalBytes)
               : (status.is_downloaded ? 100 : null);
@@ -474,9 +532,11 @@ window.addEventListener("DOMContentLoaded", () => {
               ? 'Preparing download…'
               : effectiveAction === 'refresh'
                 ? 'Refreshing…'
-                : effectiveAction === 'remove'
-                  ? 'Removing…'
-                  : null;
+                : effectiveAction === 'cancel'
+                  ? 'Cancelling…'
+                  : effectiveAction === 'remove'
+                    ? 'Removing…'
+                    : null;
         state.error = undefined;
         updateCardUI(modelName);
 
@@ -485,6 +545,8 @@ window.addEventListener("DOMContentLoaded", () => {
             await invoke('start_model_download', { modelName });
           } else if (action === 'refresh') {
             await invoke('refresh_model_download', { modelName });
+          } else if (action === 'cancel') {
+            await invoke('cancel_model_download', { modelName });
           } else if (action === 'confirm-remove') {
             await invoke('remove_model', { modelName });
             state.isConfirmingRemoval = false;
@@ -556,6 +618,12 @@ window.addEventListener("DOMContentLoaded", () => {
         const payload = event.payload as DownloadEventPayload;
         const state = ensureState(payload.modelName);
 
+        state.isVerifying = payload.status === 'verifying';
+        state.isQueued = payload.status === 'queued' || payload.status === 'refreshing';
+        state.isPaused = payload.status === 'paused';
+        state.bytesPerSecond = payload.bytesPerSecond ?? null;
+        state.etaSeconds = payload.etaSeconds ?? null;
+
         switch (payload.status) {
           case 'queued':
           case 'refreshing':
@@ -569,6 +637,18 @@ window.addEventListener("DOMContentLoaded", () => {
               state.isDownloaded = false;
             }
             break;
+          case 'paused':
+            state.isDownloading = true;
+            break;
+          case 'resuming':
+            state.isDownloading = true;
+            state.isDownloaded = false;
+            state.error = undefined;
+            state.resumeOffset = payload.resumeOffset ?? null;
+            state.downloadedBytes = payload.downloadedBytes ?? 0;
+            state.totalBytes = payload.totalBytes ?? state.totalBytes;
+            state.progressPercent = payload.percent ?? computePercent(state.downloadedBytes, state.totalBytes);
+            break;
           case 'downloading':
             state.isDownloading = true;
             state.error = undefined;
@@ -583,6 +663,14 @@ window.addEventListener("DOMContentLoaded", () => {
             state.downloadedBytes = payload.downloadedBytes ?? state.downloadedBytes;
             state.totalBytes = payload.totalBytes ?? state.totalBytes;
             state.progressPercent = 100;
+            state.resumeOffset = null;
+            break;
+          case 'cancelled':
+            state.isDownloading = false;
+            state.error = undefined;
+            state.downloadedBytes = 0;
+            state.progressPercent = null;
+            state.resumeOffset = null;
             break;
           case 'removed':
             state.isDownloading = false;
@@ -591,12 +679,22 @@ window.addEventListener("DOMContentLoaded", () => {
             state.downloadedBytes = 0;
             state.totalBytes = null;
             state.progressPercent = null;
+            state.resumeOffset = null;
+            break;
+          case 'verifying':
+            state.isDownloading = true;
+            state.progressPercent = 100;
             break;
           case 'error':
             state.isDownloading = false;
             state.error = payload.error ?? 'Download failed';
             state.progressPercent = null;
             break;
+          case 'verification_failed':
+            state.isDownloading = false;
+            state.error = payload.error ?? 'Checksum verification failed';
+            state.progressPercent = null;
+            break;
           case 'active':
             state.isDownloaded = true;
             state.progressPercent = 100;
@@ -607,7 +705,7 @@ window.addEventListener("DOMContentLoaded", () => {
 
         updateCardUI(payload.modelName);
 
-        if (['completed', 'error', 'removed', 'active'].includes(payload.status)) {
+        if (['queued', 'paused', 'completed', 'error', 'verification_failed', 'cancelled', 'removed', 'active'].includes(payload.status)) {
           void refreshStatuses();
         }
       });